@@ -27,6 +27,37 @@ impl<A: Aleo> Response<A> {
         output_types: &[console::ValueType<A::Network>], // Note: Console type
         output_registers: &[console::Register<A::Network>], // Note: Console type
     ) -> Self {
+        Self::from_outputs_with_profile(program_id, num_inputs, tvk, tcm, outputs, output_types, output_registers).0
+    }
+
+    /// Initializes a response, given the number of inputs, caller, tvk, tcm, outputs, output types, and output registers,
+    /// and returns the circuit counts contributed by each kind of output.
+    pub fn from_outputs_with_profile(
+        program_id: &ProgramID<A>,
+        num_inputs: usize,
+        tvk: &Field<A>,
+        tcm: &Field<A>,
+        outputs: Vec<Value<A>>,
+        output_types: &[console::ValueType<A::Network>], // Note: Console type
+        output_registers: &[console::Register<A::Network>], // Note: Console type
+    ) -> (Self, ResponseProfile) {
+        // Ensure the record outputs use distinct output registers, as the register locator
+        // determines the encryption randomizer (and nonce) of each record output.
+        // Note: No constraint is needed for this check, as the output registers are fixed by the function,
+        // and not witnessed. A function with a duplicate register halts during synthesis, so no circuit
+        // (and thus no verifying key) can be produced for it.
+        let record_locators = output_types
+            .iter()
+            .zip_eq(output_registers)
+            .filter(|(output_type, _)| matches!(output_type, console::ValueType::Record(..)))
+            .map(|(_, output_register)| output_register.locator());
+        if has_duplicates(record_locators) {
+            A::halt("Found a duplicate output register for the record outputs")
+        }
+
+        // Initialize the profile.
+        let mut profile = ResponseProfile::default();
+
         // Compute the output IDs.
         let output_ids = outputs
             .iter()
@@ -34,7 +65,10 @@ impl<A: Aleo> Response<A> {
             .zip_eq(output_registers)
             .enumerate()
             .map(|(index, ((output, output_type), output_register))| {
-                match output_type {
+                // Retrieve the circuit counts, before computing the output ID.
+                let counts = A::count();
+
+                let output_id = match output_type {
                     // For a constant output, compute the hash (using `tcm`) of the output.
                     console::ValueType::Constant(..) => {
                         // Prepare the index as a constant field element.
@@ -125,12 +159,16 @@ impl<A: Aleo> Response<A> {
                             Value::Plaintext(..) => A::halt("Expected a record output, found a plaintext output"),
                         }
                     }
-                }
+                };
+
+                // Add the circuit counts of the output ID to the profile.
+                profile.add(output_type, counts, A::count());
+                output_id
             })
             .collect();
 
-        // Return the response.
-        Self { output_ids, outputs }
+        // Return the response and profile.
+        (Self { output_ids, outputs }, profile)
     }
 }
 
@@ -205,22 +243,45 @@ mod tests {
             let response =
                 console::Response::new(&program_id, 4, &tvk, &tcm, outputs.clone(), &output_types, &output_registers)?;
 
+            // Ensure the native verification accepts the response, and rejects it for a different `tvk`.
+            assert!(response.verify_outputs(&program_id, 4, &tvk, &output_types, &output_registers));
+            let other_tvk = console::Field::rand(rng);
+            assert!(!response.verify_outputs(&program_id, 4, &other_tvk, &output_types, &output_registers));
+
             // Inject the program ID, `tvk`, `tcm`, and outputs.
+            let console_program_id = program_id;
             let program_id = ProgramID::<Circuit>::new(mode, program_id);
             let tvk = Field::<Circuit>::new(mode, tvk);
             let tcm = Field::<Circuit>::new(mode, tcm);
             let outputs = Inject::new(mode, outputs);
 
-            Circuit::scope(format!("Response {i}"), || {
+            let candidate = Circuit::scope(format!("Response {i}"), || {
                 // Compute the response using outputs (circuit).
-                let candidate =
-                    Response::from_outputs(&program_id, 4, &tvk, &tcm, outputs, &output_types, &output_registers);
+                let (candidate, profile) = Response::from_outputs_with_profile(
+                    &program_id,
+                    4,
+                    &tvk,
+                    &tcm,
+                    outputs,
+                    &output_types,
+                    &output_registers,
+                );
                 assert_eq!(response, candidate.eject_value());
+                // Ensure the per-output counts of the profile sum to the counts of the scope.
+                assert_eq!(profile.total(), Circuit::count_in_scope());
+                assert!(profile.record().0 > 0);
                 match mode.is_constant() {
                     true => assert_scope!(<=num_constants, <=num_public, <=num_private, <=num_constraints),
                     false => assert_scope!(<=num_constants, num_public, num_private, num_constraints),
                 }
+                candidate.eject_value()
             });
+
+            // Ensure the native verification matches the circuit verification.
+            assert!(Circuit::is_satisfied());
+            let tvk = tvk.eject_value();
+            assert!(candidate.verify_outputs(&console_program_id, 4, &tvk, &output_types, &output_registers));
+            assert!(!candidate.verify_outputs(&console_program_id, 4, &other_tvk, &output_types, &output_registers));
             Circuit::reset();
         }
         Ok(())
@@ -229,6 +290,29 @@ mod tests {
     // Note: These counts are correct. At this (high) level of a program, we override the default mode in many cases,
     // based on the user-defined visibility in the types. Thus, we have nonzero public, private, and constraint values.
 
+    #[test]
+    #[should_panic(expected = "Found a duplicate output register for the record outputs")]
+    fn test_from_outputs_duplicate_record_register() {
+        // Construct two record outputs.
+        let output_record = console::Value::<<Circuit as Environment>::Network>::Record(console::Record::from_str("{ owner: aleo1d5hg2z3ma00382pngntdp68e74zv54jdxy249qhaujhks9c72yrs33ddah.private, gates: 5u64.private, token_amount: 100u64.private, _nonce: 0group.public }").unwrap());
+        let outputs = Inject::new(Mode::Private, vec![output_record.clone(), output_record]);
+
+        // Construct the output types and registers, with a duplicate output register.
+        let output_types = vec![
+            console::ValueType::from_str("token.record").unwrap(),
+            console::ValueType::from_str("token.record").unwrap(),
+        ];
+        let output_registers = vec![console::Register::Locator(8), console::Register::Locator(8)];
+
+        // Inject the program ID, `tvk`, and `tcm`.
+        let program_id = ProgramID::<Circuit>::new(Mode::Constant, console::ProgramID::from_str("test.aleo").unwrap());
+        let tvk = Field::<Circuit>::new(Mode::Private, console::Field::rand(&mut TestRng::default()));
+        let tcm = Field::<Circuit>::new(Mode::Private, console::Field::rand(&mut TestRng::default()));
+
+        // Ensure the response halts during synthesis.
+        Response::from_outputs(&program_id, 4, &tvk, &tcm, outputs, &output_types, &output_registers);
+    }
+
     #[test]
     fn test_from_outputs_constant() -> Result<()> {
         check_from_outputs(Mode::Constant, 19100, 6, 9400, 9400)
@@ -0,0 +1,94 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use console::Network;
+
+/// The number of constants, public variables, private variables, constraints, and gates in a circuit.
+pub type CircuitCounts = (u64, u64, u64, u64, u64);
+
+/// The circuit counts contributed by each kind of output, when computing a response.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResponseProfile {
+    /// The circuit counts of the constant outputs.
+    constant: CircuitCounts,
+    /// The circuit counts of the public outputs.
+    public: CircuitCounts,
+    /// The circuit counts of the private outputs.
+    private: CircuitCounts,
+    /// The circuit counts of the record outputs.
+    record: CircuitCounts,
+    /// The circuit counts of the external record outputs.
+    external_record: CircuitCounts,
+}
+
+impl ResponseProfile {
+    /// Returns the circuit counts of the constant outputs.
+    pub const fn constant(&self) -> CircuitCounts {
+        self.constant
+    }
+
+    /// Returns the circuit counts of the public outputs.
+    pub const fn public(&self) -> CircuitCounts {
+        self.public
+    }
+
+    /// Returns the circuit counts of the private outputs.
+    pub const fn private(&self) -> CircuitCounts {
+        self.private
+    }
+
+    /// Returns the circuit counts of the record outputs.
+    pub const fn record(&self) -> CircuitCounts {
+        self.record
+    }
+
+    /// Returns the circuit counts of the external record outputs.
+    pub const fn external_record(&self) -> CircuitCounts {
+        self.external_record
+    }
+
+    /// Returns the circuit counts of all outputs.
+    pub fn total(&self) -> CircuitCounts {
+        [self.constant, self.public, self.private, self.record, self.external_record]
+            .into_iter()
+            .fold((0, 0, 0, 0, 0), add)
+    }
+
+    /// Adds the difference between the given circuit counts, to the circuit counts of the given output type.
+    pub(super) fn add<N: Network>(
+        &mut self,
+        output_type: &console::ValueType<N>,
+        before: CircuitCounts,
+        after: CircuitCounts,
+    ) {
+        let counts = match output_type {
+            console::ValueType::Constant(..) => &mut self.constant,
+            console::ValueType::Public(..) => &mut self.public,
+            console::ValueType::Private(..) => &mut self.private,
+            console::ValueType::Record(..) => &mut self.record,
+            console::ValueType::ExternalRecord(..) => &mut self.external_record,
+        };
+        *counts = add(
+            *counts,
+            (after.0 - before.0, after.1 - before.1, after.2 - before.2, after.3 - before.3, after.4 - before.4),
+        );
+    }
+}
+
+/// Returns the sum of the given circuit counts.
+const fn add(a: CircuitCounts, b: CircuitCounts) -> CircuitCounts {
+    (a.0 + b.0, a.1 + b.1, a.2 + b.2, a.3 + b.3, a.4 + b.4)
+}
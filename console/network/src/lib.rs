@@ -72,6 +72,8 @@ pub trait Network:
     const NAME: &'static str;
     /// The network edition.
     const EDITION: u16;
+    /// The expected genesis block hash, if the network has a canonical genesis block.
+    const GENESIS_HASH: Option<&'static str> = None;
 
     /// The maximum recursive depth of a value and/or entry.
     /// Note: This value must be strictly less than u8::MAX.
@@ -88,12 +90,35 @@ pub trait Network:
     const MAX_INSTRUCTIONS: usize = u16::MAX as usize;
     /// The maximum number of commands in finalize.
     const MAX_COMMANDS: usize = u8::MAX as usize;
+    /// The maximum number of finalize commands evaluated per transaction.
+    const MAX_FINALIZE_STEPS: usize = 4 * Self::MAX_COMMANDS;
 
     /// The maximum number of inputs per transition.
     const MAX_INPUTS: usize = 8;
     /// The maximum number of outputs per transition.
     const MAX_OUTPUTS: usize = 8;
 
+    /// The maximum number of transitions per block.
+    const MAX_TRANSITIONS_PER_BLOCK: usize = u16::MAX as usize;
+    /// The depth of the Merkle tree for the blocks.
+    /// Note: The ledger currently supports only a depth of 32, as the state path circuit is fixed to it.
+    const BLOCKS_DEPTH: u8 = 32;
+    /// The expected time between consecutive blocks (i.e. rounds), in seconds.
+    /// Note: This value must be positive.
+    const BLOCK_TIME_SECONDS: i64 = 10;
+    /// The maximum number of seconds that a block timestamp may trail the time implied by its round.
+    const MAX_ROUND_TIMESTAMP_SKEW_IN_SECS: i64 = 10;
+    /// The maximum size of a transaction in the memory pool, in bytes (4 MiB).
+    const MAX_TRANSACTION_BYTES: usize = 1 << 22;
+    /// The minimum balance (in gates) of a record output with a public balance, for a transaction to enter the memory pool.
+    /// Note: A value of zero disables the dust threshold.
+    const DUST_THRESHOLD_IN_GATES: u64 = 0;
+    /// The number of blocks between retargets, which determines the anchor block for target computation.
+    /// Note: This value must be nonzero.
+    const ANCHOR_BLOCK_INTERVAL: u32 = 256;
+    /// The maximum number of blocks that may be rolled back below the latest block.
+    const MAX_REORG_DEPTH: u32 = 100;
+
     /// The state root type.
     type StateRoot: Bech32ID<Field<Self>>;
     /// The block hash type.
@@ -118,8 +118,12 @@ impl Network for Testnet3 {
     /// The transition ID type.
     type TransitionID = AleoID<Field<Self>, { hrp2!("as") }>;
 
+    /// The expected time between consecutive blocks, in seconds.
+    const BLOCK_TIME_SECONDS: i64 = 10;
     /// The network edition.
     const EDITION: u16 = 0;
+    /// The genesis block hash.
+    const GENESIS_HASH: Option<&'static str> = Some("ab1705fwk5nqlvknx6d5zjh4gfpv5l5qhr94qawvlq786gqkrgqy5rqpgy4ys");
     /// The network ID.
     const ID: u16 = 3;
     /// The network name.
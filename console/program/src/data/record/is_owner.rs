@@ -17,8 +17,18 @@
 use super::*;
 
 impl<N: Network> Record<N, Ciphertext<N>> {
-    /// Decrypts `self` into plaintext using the given view key & nonce.
-    pub fn is_owner(&self, address: &Address<N>, view_key: &ViewKey<N>) -> bool {
+    /// Returns `true` if the given view key corresponds to the owner of the record.
+    /// Note: This method checks the owner of the record, without decrypting the remainder of the record.
+    pub fn is_owner(&self, view_key: &ViewKey<N>) -> bool {
+        // Derive the address from the view key.
+        let address = view_key.to_address();
+        // Check if the address is the owner.
+        self.is_owner_with_address(&address, view_key)
+    }
+
+    /// Returns `true` if the given address and view key correspond to the owner of the record.
+    /// Note: This method is faster than `is_owner`, when the address is precomputed.
+    pub fn is_owner_with_address(&self, address: &Address<N>, view_key: &ViewKey<N>) -> bool {
         match &self.owner {
             // If the owner is public, check if the address is the owner.
             Owner::Public(owner) => owner == address,
@@ -81,7 +91,10 @@ mod tests {
         let address = Address::try_from(&view_key)?;
 
         // Ensure the record belongs to the owner.
-        assert!(ciphertext.is_owner(&address, &view_key));
+        assert!(ciphertext.is_owner(&view_key));
+        assert!(ciphertext.is_owner_with_address(&address, &view_key));
+        // Ensure the ownership check matches the decrypted record.
+        assert!(matches!(ciphertext.decrypt(&view_key), Ok(record) if **record.owner() == address));
 
         // Sample a random view key and address.
         let private_key = PrivateKey::<N>::new(rng)?;
@@ -89,7 +102,8 @@ mod tests {
         let address = Address::try_from(&private_key)?;
 
         // Ensure the random address is not the owner.
-        assert!(!ciphertext.is_owner(&address, &view_key));
+        assert!(!ciphertext.is_owner(&view_key));
+        assert!(!ciphertext.is_owner_with_address(&address, &view_key));
 
         Ok(())
     }
@@ -33,6 +33,7 @@ mod serialize;
 mod to_bits;
 mod to_commitment;
 mod to_fields;
+mod to_tag;
 
 use crate::{Ciphertext, Identifier, Literal, Plaintext, ProgramID};
 use snarkvm_console_account::{Address, ViewKey};
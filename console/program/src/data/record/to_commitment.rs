@@ -26,6 +26,19 @@ impl<N: Network> Record<N, Plaintext<N>> {
         // Compute the BHP hash of the program record.
         N::hash_bhp1024(&input)
     }
+
+    /// Returns `true` if the given commitment is the record commitment, for the given program ID and record name.
+    pub fn verify_commitment(
+        &self,
+        program_id: &ProgramID<N>,
+        record_name: &Identifier<N>,
+        expected: &Field<N>,
+    ) -> bool {
+        match self.to_commitment(program_id, record_name) {
+            Ok(commitment) => commitment == *expected,
+            Err(_) => false,
+        }
+    }
 }
 
 impl<N: Network> Record<N, Ciphertext<N>> {
@@ -34,3 +47,33 @@ impl<N: Network> Record<N, Ciphertext<N>> {
         bail!("Illegal operation: Record::to_commitment() cannot be invoked on the `Ciphertext` variant.")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_verify_commitment() -> Result<()> {
+        let mut rng = TestRng::default();
+
+        let record = Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::from_str(
+            "{ owner: aleo14tlamssdmg3d0p5zmljma573jghe2q9n6wz29qf36re2glcedcpqfg4add.private, gates: 5u64.private, a: true.private, _nonce: 0group.public }",
+        )?;
+        let program_id = ProgramID::from_str("token.aleo")?;
+        let record_name = Identifier::from_str("token")?;
+
+        // Ensure the record commitment is verified.
+        let commitment = record.to_commitment(&program_id, &record_name)?;
+        assert!(record.verify_commitment(&program_id, &record_name, &commitment));
+
+        // Ensure a mismatching commitment is rejected.
+        assert!(!record.verify_commitment(&program_id, &record_name, &Field::rand(&mut rng)));
+        // Ensure the commitment is bound to the program ID and record name.
+        assert!(!record.verify_commitment(&ProgramID::from_str("other.aleo")?, &record_name, &commitment));
+        assert!(!record.verify_commitment(&program_id, &Identifier::from_str("other")?, &commitment));
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+/// The kind of an output value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OutputKind {
+    /// A plaintext output.
+    Plaintext,
+    /// A record output.
+    Record,
+}
+
+impl<N: Network> From<&Value<N>> for OutputKind {
+    /// Returns the kind of the given output value.
+    fn from(value: &Value<N>) -> Self {
+        match value {
+            Value::Plaintext(..) => Self::Plaintext,
+            Value::Record(..) => Self::Record,
+        }
+    }
+}
+
+impl Display for OutputKind {
+    /// Prints the output kind as a string.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Plaintext => write!(f, "plaintext"),
+            Self::Record => write!(f, "record"),
+        }
+    }
+}
+
+/// A distinguished error that may occur when constructing a response.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ResponseError {
+    /// The output at the given index does not match the kind of its declared output type.
+    OutputTypeMismatch { index: usize, expected: OutputKind, found: OutputKind },
+}
+
+impl Display for ResponseError {
+    /// Prints the response error as a string.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::OutputTypeMismatch { index, expected, found } => {
+                write!(f, "Expected a {expected} output at index {index}, found a {found} output")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ResponseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    /// Returns the response error for the given outputs and output types.
+    fn check_mismatch(outputs: Vec<Value<CurrentNetwork>>, output_types: &[&str]) -> ResponseError {
+        let rng = &mut TestRng::default();
+
+        let output_types = output_types.iter().map(|t| ValueType::from_str(t).unwrap()).collect::<Vec<_>>();
+        let output_registers = (0..outputs.len() as u64).map(|i| Register::Locator(5 + i)).collect::<Vec<_>>();
+        let program_id = ProgramID::from_str("test.aleo").unwrap();
+        let tvk = Field::rand(rng);
+        let tcm = CurrentNetwork::hash_psd2(&[tvk]).unwrap();
+
+        let error = Response::new(&program_id, 4, &tvk, &tcm, outputs, &output_types, &output_registers).unwrap_err();
+        *error.downcast_ref::<ResponseError>().unwrap()
+    }
+
+    #[test]
+    fn test_output_type_mismatch() {
+        let plaintext = Value::<CurrentNetwork>::from_str("{ token_amount: 9876543210u128 }").unwrap();
+        let record = Value::<CurrentNetwork>::from_str(
+            "{ owner: aleo1d5hg2z3ma00382pngntdp68e74zv54jdxy249qhaujhks9c72yrs33ddah.private, gates: 5u64.private, _nonce: 0group.public }",
+        )
+        .unwrap();
+
+        // Ensure a record for a plaintext type is reported at its index.
+        for output_type in ["amount.constant", "amount.public", "amount.private"] {
+            let error = check_mismatch(vec![plaintext.clone(), record.clone()], &["amount.public", output_type]);
+            assert_eq!(error, ResponseError::OutputTypeMismatch {
+                index: 1,
+                expected: OutputKind::Plaintext,
+                found: OutputKind::Record
+            });
+        }
+
+        // Ensure a plaintext for a record type is reported at its index.
+        for output_type in ["token.record", "token.aleo/token.record"] {
+            let error = check_mismatch(vec![plaintext.clone()], &[output_type]);
+            assert_eq!(error, ResponseError::OutputTypeMismatch {
+                index: 0,
+                expected: OutputKind::Record,
+                found: OutputKind::Plaintext
+            });
+            assert_eq!(error.to_string(), "Expected a record output at index 0, found a plaintext output");
+        }
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use core::ops::BitOr;
+
+/// A set of output ID kinds, selecting which output IDs are checked in response verification.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OutputIDFlags(u8);
+
+impl OutputIDFlags {
+    /// Selects all output IDs.
+    pub const ALL: Self = Self(0b11111);
+    /// Selects the constant output IDs.
+    pub const CONSTANT: Self = Self(1 << 0);
+    /// Selects the external record output IDs.
+    pub const EXTERNAL_RECORD: Self = Self(1 << 4);
+    /// Selects no output IDs.
+    pub const NONE: Self = Self(0);
+    /// Selects the private output IDs.
+    pub const PRIVATE: Self = Self(1 << 2);
+    /// Selects the public output IDs.
+    pub const PUBLIC: Self = Self(1 << 1);
+    /// Selects the record output IDs.
+    pub const RECORD: Self = Self(1 << 3);
+
+    /// Returns the flag for the kind of the given output ID.
+    pub const fn of<N: Network>(output_id: &OutputID<N>) -> Self {
+        match output_id {
+            OutputID::Constant(..) => Self::CONSTANT,
+            OutputID::Public(..) => Self::PUBLIC,
+            OutputID::Private(..) => Self::PRIVATE,
+            OutputID::Record(..) => Self::RECORD,
+            OutputID::ExternalRecord(..) => Self::EXTERNAL_RECORD,
+        }
+    }
+
+    /// Returns `true` if all of the given flags are selected.
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if the kind of the given output ID is selected.
+    pub const fn selects<N: Network>(&self, output_id: &OutputID<N>) -> bool {
+        self.contains(Self::of(output_id))
+    }
+}
+
+impl Default for OutputIDFlags {
+    /// Returns the flags selecting all output IDs.
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl BitOr for OutputIDFlags {
+    type Output = Self;
+
+    /// Returns the union of the given flags.
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_output_id_flags() {
+        let rng = &mut TestRng::default();
+
+        let output_ids = [
+            OutputID::<CurrentNetwork>::Constant(Uniform::rand(rng)),
+            OutputID::Public(Uniform::rand(rng)),
+            OutputID::Private(Uniform::rand(rng)),
+            OutputID::Record(Uniform::rand(rng), Uniform::rand(rng)),
+            OutputID::ExternalRecord(Uniform::rand(rng)),
+        ];
+
+        // Ensure each output ID is selected by exactly its own flag, and by all flags.
+        let flags = [
+            OutputIDFlags::CONSTANT,
+            OutputIDFlags::PUBLIC,
+            OutputIDFlags::PRIVATE,
+            OutputIDFlags::RECORD,
+            OutputIDFlags::EXTERNAL_RECORD,
+        ];
+        for (i, output_id) in output_ids.iter().enumerate() {
+            for (j, flag) in flags.iter().enumerate() {
+                assert_eq!(flag.selects(output_id), i == j);
+            }
+            assert!(OutputIDFlags::ALL.selects(output_id));
+            assert!(OutputIDFlags::default().selects(output_id));
+            assert!(!OutputIDFlags::NONE.selects(output_id));
+        }
+
+        // Ensure the union of the flags selects all output IDs.
+        assert_eq!(flags.into_iter().fold(OutputIDFlags::NONE, BitOr::bitor), OutputIDFlags::ALL);
+        assert!((OutputIDFlags::PUBLIC | OutputIDFlags::RECORD).contains(OutputIDFlags::RECORD));
+        assert!(!(OutputIDFlags::PUBLIC | OutputIDFlags::RECORD).contains(OutputIDFlags::PRIVATE));
+    }
+}
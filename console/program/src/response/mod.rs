@@ -14,6 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
+mod error;
+pub use error::*;
+
+mod flags;
+pub use flags::*;
+
+mod verify;
+
 use crate::{ProgramID, Register, Value, ValueType};
 use snarkvm_console_network::Network;
 use snarkvm_console_types::prelude::*;
@@ -58,6 +66,13 @@ impl<N: Network> Response<N> {
         output_types: &[ValueType<N>],
         output_registers: &[Register<N>],
     ) -> Result<Self> {
+        // Ensure the record outputs use distinct output registers, as the register locator
+        // determines the encryption randomizer (and nonce) of each record output.
+        ensure!(
+            !has_duplicates(Self::record_output_locators(output_types, output_registers)),
+            "Found a duplicate output register for the record outputs"
+        );
+
         // Compute the output IDs.
         let output_ids = outputs
             .iter()
@@ -69,7 +84,7 @@ impl<N: Network> Response<N> {
                     // For a constant output, compute the hash (using `tcm`) of the output.
                     ValueType::Constant(..) => {
                         // Ensure the output is a plaintext.
-                        ensure!(matches!(output, Value::Plaintext(..)), "Expected a plaintext output");
+                        Self::check_output_kind(index, output, OutputKind::Plaintext)?;
 
                         // Construct the (console) output index as a field element.
                         let index = Field::from_u16(
@@ -88,7 +103,7 @@ impl<N: Network> Response<N> {
                     // For a public output, compute the hash (using `tcm`) of the output.
                     ValueType::Public(..) => {
                         // Ensure the output is a plaintext.
-                        ensure!(matches!(output, Value::Plaintext(..)), "Expected a plaintext output");
+                        Self::check_output_kind(index, output, OutputKind::Plaintext)?;
 
                         // Construct the (console) output index as a field element.
                         let index = Field::from_u16(
@@ -107,7 +122,7 @@ impl<N: Network> Response<N> {
                     // For a private output, compute the ciphertext (using `tvk`) and hash the ciphertext.
                     ValueType::Private(..) => {
                         // Ensure the output is a plaintext.
-                        ensure!(matches!(output, Value::Plaintext(..)), "Expected a plaintext output");
+                        Self::check_output_kind(index, output, OutputKind::Plaintext)?;
                         // Construct the (console) output index as a field element.
                         let index = Field::from_u16(
                             u16::try_from(num_inputs + index).or_halt_with::<N>("Output index exceeds u16"),
@@ -130,8 +145,12 @@ impl<N: Network> Response<N> {
                         // Retrieve the record.
                         let record = match &output {
                             Value::Record(record) => record,
-                            // Ensure the input is a record.
-                            Value::Plaintext(..) => bail!("Expected a record output, found a plaintext output"),
+                            // Ensure the output is a record.
+                            Value::Plaintext(..) => bail!(ResponseError::OutputTypeMismatch {
+                                index,
+                                expected: OutputKind::Record,
+                                found: OutputKind::Plaintext
+                            }),
                         };
 
                         // Compute the record commitment.
@@ -153,7 +172,7 @@ impl<N: Network> Response<N> {
                     // For a locator output, compute the hash (using `tvk`) of the output.
                     ValueType::ExternalRecord(..) => {
                         // Ensure the output is a record.
-                        ensure!(matches!(output, Value::Record(..)), "Expected a record output");
+                        Self::check_output_kind(index, output, OutputKind::Record)?;
 
                         // Construct the (console) output index as a field element.
                         let index = Field::from_u16(
@@ -176,6 +195,27 @@ impl<N: Network> Response<N> {
         Ok(Self { output_ids, outputs })
     }
 
+    /// Ensures the output at the given index is of the expected kind.
+    fn check_output_kind(index: usize, output: &Value<N>, expected: OutputKind) -> Result<()> {
+        let found = OutputKind::from(output);
+        if found != expected {
+            bail!(ResponseError::OutputTypeMismatch { index, expected, found })
+        }
+        Ok(())
+    }
+
+    /// Returns the output register locators, for all outputs that are records.
+    fn record_output_locators<'a>(
+        output_types: &'a [ValueType<N>],
+        output_registers: &'a [Register<N>],
+    ) -> impl 'a + Iterator<Item = u64> {
+        output_types
+            .iter()
+            .zip_eq(output_registers)
+            .filter(|(output_type, _)| matches!(output_type, ValueType::Record(..)))
+            .map(|(_, output_register)| output_register.locator())
+    }
+
     /// Returns the output ID for the transition.
     pub fn output_ids(&self) -> &[OutputID<N>] {
         &self.output_ids
@@ -0,0 +1,283 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use subtle::{Choice, ConstantTimeEq};
+
+impl<N: Network> ConstantTimeEq for OutputID<N> {
+    /// Returns `1` if `self` and `other` are equal, and `0` otherwise.
+    /// The field elements of the output IDs are compared in constant time.
+    fn ct_eq(&self, other: &Self) -> Choice {
+        /// Returns `1` if the given field elements are equal, in constant time.
+        fn ct_eq_field<N: Network>(a: &Field<N>, b: &Field<N>) -> Choice {
+            a.to_repr().as_ref().ct_eq(b.to_repr().as_ref())
+        }
+
+        match (self, other) {
+            (Self::Constant(a), Self::Constant(b)) => ct_eq_field(a, b),
+            (Self::Public(a), Self::Public(b)) => ct_eq_field(a, b),
+            (Self::Private(a), Self::Private(b)) => ct_eq_field(a, b),
+            (Self::Record(a_commitment, a_checksum), Self::Record(b_commitment, b_checksum)) => {
+                ct_eq_field(a_commitment, b_commitment) & ct_eq_field(a_checksum, b_checksum)
+            }
+            (Self::ExternalRecord(a), Self::ExternalRecord(b)) => ct_eq_field(a, b),
+            // The output ID variants are public, and may be compared in variable time.
+            _ => Choice::from(0),
+        }
+    }
+}
+
+impl<N: Network> Response<N> {
+    /// Returns `true` if the given output IDs match the output IDs of the response, and `false` otherwise.
+    ///
+    /// Note: The output IDs are compared in constant time, to avoid leaking the commitments,
+    /// checksums, and output hashes through timing side channels.
+    pub fn verify(&self, output_ids: &[OutputID<N>]) -> bool {
+        self.verify_with_flags(output_ids, OutputIDFlags::ALL)
+    }
+
+    /// Returns `true` if the given output IDs match the output IDs of the response, and `false` otherwise,
+    /// checking only the output IDs of the kinds selected by the given flags.
+    ///
+    /// Note: A pair of output IDs is skipped only if neither kind is selected, so a selected output ID
+    /// never matches an output ID of a different kind. The number of output IDs is always checked.
+    pub fn verify_with_flags(&self, output_ids: &[OutputID<N>], flags: OutputIDFlags) -> bool {
+        // Ensure the number of output IDs matches.
+        if self.output_ids.len() != output_ids.len() {
+            eprintln!("Incorrect number of output IDs in response verification.");
+            return false;
+        }
+        // Ensure each selected output ID matches, without short-circuiting on the first mismatch.
+        let is_equal =
+            self.output_ids.iter().zip_eq(output_ids).fold(Choice::from(1), |is_equal, (candidate, expected)| {
+                match flags.selects(candidate) || flags.selects(expected) {
+                    true => is_equal & candidate.ct_eq(expected),
+                    false => is_equal,
+                }
+            });
+        bool::from(is_equal)
+    }
+
+    /// Returns `true` if the output IDs of the response are consistent with its outputs, and `false` otherwise.
+    ///
+    /// This recomputes the output IDs natively (with `tcm := Hash(tvk)`) and checks them against the response,
+    /// matching the output ID checks performed by the circuit response, without a constraint system.
+    pub fn verify_outputs(
+        &self,
+        program_id: &ProgramID<N>,
+        num_inputs: usize,
+        tvk: &Field<N>,
+        output_types: &[ValueType<N>],
+        output_registers: &[Register<N>],
+    ) -> bool {
+        // Ensure the number of outputs matches.
+        if self.outputs.len() != output_types.len() || self.outputs.len() != output_registers.len() {
+            eprintln!("Incorrect number of outputs in response verification.");
+            return false;
+        }
+        // Compute the transition commitment as `Hash(tvk)`.
+        let tcm = match N::hash_psd2(&[*tvk]) {
+            Ok(tcm) => tcm,
+            Err(error) => {
+                eprintln!("Failed to compute the transition commitment: {error}");
+                return false;
+            }
+        };
+        // Recompute the output IDs from the outputs.
+        match Self::new(program_id, num_inputs, tvk, &tcm, self.outputs.clone(), output_types, output_registers) {
+            Ok(candidate) => self.verify(candidate.output_ids()),
+            Err(error) => {
+                eprintln!("Failed to recompute the output IDs: {error}");
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::Testnet3;
+
+    type CurrentNetwork = Testnet3;
+
+    const ITERATIONS: usize = 1000;
+
+    /// Samples a random output ID, using the given variant index.
+    fn sample_output_id(variant: usize, rng: &mut TestRng) -> OutputID<CurrentNetwork> {
+        match variant % 5 {
+            0 => OutputID::Constant(Uniform::rand(rng)),
+            1 => OutputID::Public(Uniform::rand(rng)),
+            2 => OutputID::Private(Uniform::rand(rng)),
+            3 => OutputID::Record(Uniform::rand(rng), Uniform::rand(rng)),
+            _ => OutputID::ExternalRecord(Uniform::rand(rng)),
+        }
+    }
+
+    #[test]
+    fn test_ct_eq_matches_eq() {
+        let rng = &mut TestRng::default();
+
+        for i in 0..ITERATIONS {
+            // Sample two output IDs.
+            let a = sample_output_id(i, rng);
+            let b = sample_output_id(rng.gen_range(0..5), rng);
+
+            // Check matching output IDs.
+            assert_eq!(a == a, bool::from(a.ct_eq(&a)));
+            assert!(bool::from(a.ct_eq(&a.clone())));
+            // Check mismatching output IDs.
+            assert_eq!(a == b, bool::from(a.ct_eq(&b)));
+            assert_eq!(b == a, bool::from(b.ct_eq(&a)));
+
+            // Check output IDs that only differ in the record checksum.
+            if let OutputID::Record(commitment, _) = a {
+                let c = OutputID::Record(commitment, Uniform::rand(rng));
+                assert_eq!(a == c, bool::from(a.ct_eq(&c)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_verify() {
+        let rng = &mut TestRng::default();
+
+        for i in 0..ITERATIONS {
+            // Sample the output IDs.
+            let output_ids = (0..(i % 4) + 1).map(|j| sample_output_id(i + j, rng)).collect::<Vec<_>>();
+            let response = Response::<CurrentNetwork>::from((output_ids.clone(), vec![]));
+
+            // Ensure the matching output IDs are verified.
+            assert!(response.verify(&output_ids));
+
+            // Ensure a mismatching output ID is rejected.
+            let mut candidate = output_ids.clone();
+            let index = rng.gen_range(0..candidate.len());
+            candidate[index] = sample_output_id(index, rng);
+            assert_eq!(output_ids == candidate, response.verify(&candidate));
+
+            // Ensure a missing output ID is rejected.
+            assert!(!response.verify(&output_ids[1..]));
+        }
+    }
+
+    #[test]
+    fn test_verify_with_flags() {
+        let rng = &mut TestRng::default();
+
+        // Sample a public output ID and a record output ID.
+        let output_ids = vec![sample_output_id(1, rng), sample_output_id(3, rng)];
+        let response = Response::<CurrentNetwork>::from((output_ids.clone(), vec![]));
+
+        // Mismatch the public output ID.
+        let mut candidate = output_ids.clone();
+        candidate[0] = sample_output_id(1, rng);
+
+        // Ensure the mismatched public output ID is skipped when only the record output IDs are checked.
+        assert!(response.verify_with_flags(&candidate, OutputIDFlags::RECORD));
+        assert!(response.verify_with_flags(&candidate, OutputIDFlags::NONE));
+        // Ensure the mismatched public output ID is rejected when the public output IDs are checked.
+        assert!(!response.verify_with_flags(&candidate, OutputIDFlags::PUBLIC));
+        assert!(!response.verify_with_flags(&candidate, OutputIDFlags::PUBLIC | OutputIDFlags::RECORD));
+        assert!(!response.verify(&candidate));
+
+        // Ensure a mismatched record output ID is rejected when the record output IDs are checked.
+        let mut candidate = output_ids.clone();
+        candidate[1] = sample_output_id(3, rng);
+        assert!(!response.verify_with_flags(&candidate, OutputIDFlags::RECORD));
+        assert!(response.verify_with_flags(&candidate, OutputIDFlags::PUBLIC));
+
+        // Ensure a selected output ID does not match an output ID of an unselected kind.
+        let mut candidate = output_ids.clone();
+        candidate[1] = sample_output_id(2, rng);
+        assert!(!response.verify_with_flags(&candidate, OutputIDFlags::RECORD));
+
+        // Ensure a missing output ID is rejected, regardless of the flags.
+        assert!(!response.verify_with_flags(&output_ids[1..], OutputIDFlags::NONE));
+    }
+
+    #[test]
+    fn test_verify_outputs() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Construct the outputs, output types, and output registers.
+        let output = Value::<CurrentNetwork>::from_str("{ token_amount: 9876543210u128 }")?;
+        let outputs = vec![output.clone(), output.clone(), output];
+        let output_types = vec![
+            ValueType::from_str("amount.constant")?,
+            ValueType::from_str("amount.public")?,
+            ValueType::from_str("amount.private")?,
+        ];
+        let output_registers = vec![Register::Locator(5), Register::Locator(6), Register::Locator(7)];
+        // Construct a program ID.
+        let program_id = ProgramID::from_str("test.aleo")?;
+
+        for _ in 0..10 {
+            // Sample a `tvk`, and compute the transition commitment as `Hash(tvk)`.
+            let tvk = Field::rand(rng);
+            let tcm = CurrentNetwork::hash_psd2(&[tvk])?;
+
+            // Construct the response.
+            let response =
+                Response::new(&program_id, 4, &tvk, &tcm, outputs.clone(), &output_types, &output_registers)?;
+
+            // Ensure the response is verified.
+            assert!(response.verify_outputs(&program_id, 4, &tvk, &output_types, &output_registers));
+            // Ensure the response is rejected for a different `tvk` or number of inputs.
+            assert!(!response.verify_outputs(&program_id, 4, &Field::rand(rng), &output_types, &output_registers));
+            assert!(!response.verify_outputs(&program_id, 3, &tvk, &output_types, &output_registers));
+            // Ensure the response is rejected for mismatched output types.
+            assert!(!response.verify_outputs(&program_id, 4, &tvk, &output_types[1..], &output_registers));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_outputs_rejects_reused_record_register() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Sample a `tvk`, and compute the transition commitment as `Hash(tvk)`.
+        let tvk = Field::rand(rng);
+        let tcm = CurrentNetwork::hash_psd2(&[tvk])?;
+
+        // Constructs a record output, with the nonce for the given output register locator.
+        let sample_record = |locator: u64| -> Result<Value<CurrentNetwork>> {
+            let randomizer = CurrentNetwork::hash_to_scalar_psd2(&[tvk, Field::from_u64(locator)])?;
+            let nonce = CurrentNetwork::g_scalar_multiply(&randomizer);
+            Value::from_str(&format!(
+                "{{ owner: aleo1d5hg2z3ma00382pngntdp68e74zv54jdxy249qhaujhks9c72yrs33ddah.private, gates: 5u64.private, _nonce: {nonce}.public }}"
+            ))
+        };
+        let output_types = vec![ValueType::from_str("token.record")?, ValueType::from_str("token.record")?];
+        // Construct a program ID.
+        let program_id = ProgramID::from_str("test.aleo")?;
+
+        // Ensure distinct output registers are accepted.
+        let outputs = vec![sample_record(5)?, sample_record(6)?];
+        let output_registers = vec![Register::Locator(5), Register::Locator(6)];
+        let response = Response::new(&program_id, 4, &tvk, &tcm, outputs, &output_types, &output_registers)?;
+        assert!(response.verify_outputs(&program_id, 4, &tvk, &output_types, &output_registers));
+
+        // Ensure a reused output register (and thus a reused randomizer) is rejected.
+        let outputs = vec![sample_record(5)?, sample_record(5)?];
+        let reused_registers = vec![Register::Locator(5), Register::Locator(5)];
+        assert!(Response::new(&program_id, 4, &tvk, &tcm, outputs.clone(), &output_types, &reused_registers).is_err());
+        let response = Response::from((response.output_ids().to_vec(), outputs));
+        assert!(!response.verify_outputs(&program_id, 4, &tvk, &output_types, &reused_registers));
+        Ok(())
+    }
+}
@@ -76,7 +76,11 @@ impl<N: Network, B: 'static + BlockStorage<N>, P: 'static + ProgramStorage<N>> S
                         let transaction_id = transaction.id();
                         // Add the transaction to the memory pool.
                         match ledger.write().add_to_memory_pool(transaction) {
-                            Ok(()) => trace!("✉️ Added transaction '{transaction_id}' to the memory pool"),
+                            Ok(None) => trace!("✉️ Added transaction '{transaction_id}' to the memory pool"),
+                            Ok(Some(replaced)) => trace!(
+                                "✉️ Added transaction '{transaction_id}' to the memory pool, replacing transaction '{}'",
+                                replaced.id()
+                            ),
                             Err(error) => {
                                 warn!("⚠️ Failed to add transaction '{transaction_id}' to the memory pool: {error}")
                             }
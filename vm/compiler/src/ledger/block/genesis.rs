@@ -26,12 +26,25 @@ impl<N: Network> Block<N> {
     ) -> Result<Self> {
         // Prepare the caller.
         let caller = Address::try_from(private_key)?;
+        // Mint the genesis supply to the caller.
+        Self::genesis_with_recipient(vm, private_key, caller, 1_100_000_000_000_000, rng)
+    }
+
+    /// Initializes a new genesis block, signed by the given private key, with the given supply (in gates)
+    /// minted to the given recipient.
+    pub fn genesis_with_recipient<P: ProgramStorage<N>, R: Rng + CryptoRng>(
+        vm: &VM<N, P>,
+        private_key: &PrivateKey<N>,
+        recipient: Address<N>,
+        supply: u64,
+        rng: &mut R,
+    ) -> Result<Self> {
         // Prepare the program ID.
         let program_id = FromStr::from_str("credits.aleo")?;
         // Prepare the function name.
         let function_name = FromStr::from_str("genesis")?;
         // Prepare the function inputs.
-        let inputs = [Value::from_str(&caller.to_string())?, Value::from_str("1_100_000_000_000_000_u64")?];
+        let inputs = [Value::from_str(&recipient.to_string())?, Value::from_str(&format!("{supply}u64"))?];
         // Authorize the call to start.
         let authorization = vm.authorize(private_key, &program_id, function_name, &inputs, rng)?;
         // Execute the genesis function.
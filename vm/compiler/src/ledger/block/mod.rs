@@ -24,6 +24,7 @@ mod bytes;
 mod genesis;
 mod serialize;
 mod string;
+mod verify;
 
 use crate::{
     ledger::{vm::VM, Origin, Transaction, Transition},
@@ -59,8 +60,6 @@ impl<N: Network> Block<N> {
         transactions: Transactions<N>,
         rng: &mut R,
     ) -> Result<Self> {
-        // Ensure the block is not empty.
-        ensure!(!transactions.is_empty(), "Cannot create block with no transactions");
         // Compute the block hash.
         let block_hash = N::hash_bhp1024(&[previous_hash.to_bits_le(), header.to_root()?.to_bits_le()].concat())?;
         // Sign the block hash.
@@ -80,8 +79,6 @@ impl<N: Network> Block<N> {
         transactions: Transactions<N>,
         signature: Signature<N>,
     ) -> Result<Self> {
-        // Ensure the block is not empty.
-        ensure!(!transactions.is_empty(), "Cannot create block with no transactions");
         // Compute the block hash.
         let block_hash = N::hash_bhp1024(&[previous_hash.to_bits_le(), header.to_root()?.to_bits_le()].concat())?;
         // Derive the signer address.
@@ -26,6 +26,7 @@ pub type TransactionsPath<N> = MerklePath<N, TRANSACTIONS_DEPTH>;
 
 impl<N: Network> Transactions<N> {
     /// Returns the transactions root, by computing the root for a Merkle tree of the transaction IDs.
+    /// Note: The root of an empty list of transactions is the root of the empty Merkle tree.
     pub fn to_root(&self) -> Result<Field<N>> {
         Ok(*self.to_tree()?.root())
     }
@@ -68,4 +69,13 @@ mod tests {
         // Ensure the log2 relationship between depth and the maximum number of transactions.
         assert_eq!(2usize.pow(TRANSACTIONS_DEPTH as u32), Transactions::<CurrentNetwork>::MAX_TRANSACTIONS);
     }
+
+    #[test]
+    fn test_empty_transactions_root() {
+        // Ensure an empty list of transactions has the root of the empty Merkle tree.
+        let transactions = Transactions::<CurrentNetwork>::from(&[]);
+        let empty_root = *CurrentNetwork::merkle_tree_bhp::<TRANSACTIONS_DEPTH>(&[]).unwrap().root();
+        assert_eq!(transactions.to_root().unwrap(), empty_root);
+        assert_ne!(empty_root, Field::zero());
+    }
 }
@@ -0,0 +1,98 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::ProgramStorage;
+
+use rayon::prelude::*;
+
+impl<N: Network> Block<N> {
+    /// Ensures the transactions in the block are valid, by verifying all transactions at once.
+    pub fn verify<P: ProgramStorage<N>>(&self, vm: &VM<N, P>) -> Result<()> {
+        self.verify_in_chunks(vm, self.transactions.len().max(1))
+    }
+
+    /// Returns `true` if the transactions root in the block header matches the root of the block transactions.
+    pub fn verify_transactions_root(&self) -> Result<bool> {
+        Ok(self.transactions.to_root()? == *self.header.transactions_root())
+    }
+
+    /// Ensures the transactions in the block are valid, by verifying up to `chunk_size` transactions at a time.
+    /// Note: This bounds the number of transaction proofs that are verified concurrently.
+    pub fn verify_in_chunks<P: ProgramStorage<N>>(&self, vm: &VM<N, P>, chunk_size: usize) -> Result<()> {
+        // Ensure the chunk size is nonzero.
+        ensure!(chunk_size > 0, "The chunk size for block verification must be nonzero");
+
+        let transactions = self.transactions.values().collect::<Vec<_>>();
+        for chunk in transactions.chunks(chunk_size) {
+            // Verify the transactions in the chunk.
+            if let Some(transaction) = chunk.par_iter().find_first(|transaction| !vm.verify(transaction)) {
+                bail!("Transaction '{}' in block {} failed to verify", transaction.id(), self.height())
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_utilities::TestRng;
+
+    #[test]
+    fn test_verify_in_chunks() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Propose a block with multiple transactions.
+        let deployment = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+        let execution = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(deployment).unwrap();
+        ledger.add_to_memory_pool(execution).unwrap();
+        let block = ledger.propose_next_block(&private_key, rng).unwrap();
+        assert_eq!(block.transactions().len(), 2);
+
+        // Ensure verification in chunks matches whole-block verification.
+        assert!(block.verify(ledger.vm()).is_ok());
+        for chunk_size in [1, 2, 3] {
+            assert!(block.verify_in_chunks(ledger.vm(), chunk_size).is_ok());
+        }
+        // Ensure a chunk size of zero is rejected.
+        assert!(block.verify_in_chunks(ledger.vm(), 0).is_err());
+    }
+
+    #[test]
+    fn test_verify_transactions_root() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key and block.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        let block = crate::ledger::test_helpers::sample_genesis_block_with_pk(rng, private_key);
+        // Ensure the transactions root of the block is valid.
+        assert!(block.verify_transactions_root().unwrap());
+
+        // Construct a block whose header has an incorrect transactions root.
+        let header = block.header();
+        let header = Header::from(*header.previous_state_root(), Field::from_u64(1), *header.metadata()).unwrap();
+        let block = Block::new(&private_key, block.previous_hash(), header, block.transactions().clone(), rng).unwrap();
+        // Ensure the transactions root of the block is invalid.
+        assert!(!block.verify_transactions_root().unwrap());
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+
+/// The operator-local metadata of a block, which is not part of the canonical block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockMetadata {
+    /// The UNIX timestamp at which the block was received.
+    received_at: i64,
+    /// The address of the peer that sent the block, if it was received from a peer.
+    source: Option<SocketAddr>,
+}
+
+impl BlockMetadata {
+    /// Initializes a new block metadata.
+    pub const fn new(received_at: i64, source: Option<SocketAddr>) -> Self {
+        Self { received_at, source }
+    }
+
+    /// Returns the UNIX timestamp at which the block was received.
+    pub const fn received_at(&self) -> i64 {
+        self.received_at
+    }
+
+    /// Returns the address of the peer that sent the block, if it was received from a peer.
+    pub const fn source(&self) -> Option<SocketAddr> {
+        self.source
+    }
+}
@@ -16,7 +16,9 @@
 
 use super::*;
 
-impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
+impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>, M: for<'a> Map<'a, N::TransactionID, Transaction<N>>>
+    Ledger<N, B, P, M>
+{
     /// Returns `true` if the given state root exists.
     pub fn contains_state_root(&self, _state_root: &Field<N>) -> bool {
         todo!()
@@ -90,6 +92,34 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
         self.transitions.contains_nonce(nonce)
     }
 
+    /// Returns the first of the given serial numbers, commitments, and nonces that already exists, if any.
+    pub fn contains_any(
+        &self,
+        serial_numbers: &[Field<N>],
+        commitments: &[Field<N>],
+        nonces: &[Group<N>],
+    ) -> Result<Option<DuplicateKind<N>>> {
+        let serial_numbers = serial_numbers.iter().map(|serial_number| DuplicateKind::SerialNumber(*serial_number));
+        let commitments = commitments.iter().map(|commitment| DuplicateKind::Commitment(*commitment));
+        let nonces = nonces.iter().map(|nonce| DuplicateKind::Nonce(*nonce));
+
+        for candidate in serial_numbers.chain(commitments).chain(nonces) {
+            if self.contains_duplicate(&candidate)? {
+                return Ok(Some(candidate));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns `true` if the given serial number, commitment, or nonce already exists.
+    pub(super) fn contains_duplicate(&self, candidate: &DuplicateKind<N>) -> Result<bool> {
+        match candidate {
+            DuplicateKind::SerialNumber(serial_number) => self.contains_serial_number(serial_number),
+            DuplicateKind::Commitment(commitment) => self.contains_commitment(commitment),
+            DuplicateKind::Nonce(nonce) => self.contains_nonce(nonce),
+        }
+    }
+
     /* Metadata */
 
     /// Returns `true` if the given transition public key exists.
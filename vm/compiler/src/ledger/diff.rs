@@ -0,0 +1,129 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+/// The difference between the committed blocks of two ledgers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LedgerDiff<N: Network> {
+    /// The latest block height of the first ledger.
+    latest_height: u32,
+    /// The latest block height of the second ledger.
+    other_latest_height: u32,
+    /// The first block height at which the block hashes differ, with the block hash in each ledger.
+    fork: Option<(u32, N::BlockHash, N::BlockHash)>,
+}
+
+impl<N: Network> LedgerDiff<N> {
+    /// Returns the latest block height of the first ledger.
+    pub const fn latest_height(&self) -> u32 {
+        self.latest_height
+    }
+
+    /// Returns the latest block height of the second ledger.
+    pub const fn other_latest_height(&self) -> u32 {
+        self.other_latest_height
+    }
+
+    /// Returns the first block height at which the block hashes differ, with the block hash in each ledger.
+    pub const fn fork(&self) -> Option<(u32, N::BlockHash, N::BlockHash)> {
+        self.fork
+    }
+
+    /// Returns the first block height at which the block hashes differ, if the ledgers have forked.
+    pub fn fork_height(&self) -> Option<u32> {
+        self.fork.map(|(height, ..)| height)
+    }
+
+    /// Returns `true` if the blocks of one ledger are a prefix of the blocks of the other ledger.
+    pub const fn is_prefix(&self) -> bool {
+        self.fork.is_none()
+    }
+
+    /// Returns `true` if both ledgers contain the same blocks.
+    pub const fn is_equal(&self) -> bool {
+        self.is_prefix() && self.latest_height == self.other_latest_height
+    }
+}
+
+impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>, M: for<'a> Map<'a, N::TransactionID, Transaction<N>>>
+    Ledger<N, B, P, M>
+{
+    /// Returns the difference between the committed blocks of `self` and the given ledger.
+    /// Note: The memory pools of the ledgers are not compared.
+    pub fn diff(&self, other: &Self) -> Result<LedgerDiff<N>> {
+        // Find the first block height, up to the lower of the latest heights, at which the block hashes differ.
+        let mut fork = None;
+        for height in 0..=self.latest_height().min(other.latest_height()) {
+            let (hash, other_hash) = (self.get_hash(height)?, other.get_hash(height)?);
+            if hash != other_hash {
+                fork = Some((height, hash, other_hash));
+                break;
+            }
+        }
+
+        Ok(LedgerDiff { latest_height: self.latest_height(), other_latest_height: other.latest_height(), fork })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_utilities::TestRng;
+
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_diff() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample two genesis ledgers, with separate storage.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+        let mut other_ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Ensure the genesis ledgers are equal.
+        let diff = ledger.diff(&other_ledger).unwrap();
+        assert!(diff.is_equal());
+        assert_eq!(diff.fork_height(), None);
+
+        // Add a different block at height 1 to each ledger.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Ensure the genesis ledger is a prefix of the ledger.
+        let diff = ledger.diff(&other_ledger).unwrap();
+        assert!(diff.is_prefix());
+        assert!(!diff.is_equal());
+        assert_eq!((diff.latest_height(), diff.other_latest_height()), (1, 0));
+
+        let transaction = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+        other_ledger.add_to_memory_pool(transaction).unwrap();
+        let other_next_block = other_ledger.propose_next_block(&private_key, rng).unwrap();
+        other_ledger.add_next_block(&other_next_block).unwrap();
+
+        // Ensure the diff reports the fork at height 1.
+        let diff = ledger.diff(&other_ledger).unwrap();
+        assert!(!diff.is_prefix());
+        assert_eq!(diff.fork_height(), Some(1));
+        assert_eq!(diff.fork(), Some((1, next_block.hash(), other_next_block.hash())));
+        assert_eq!(other_ledger.diff(&ledger).unwrap().fork(), Some((1, other_next_block.hash(), next_block.hash())));
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::io::ErrorKind;
+
+impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>, M: for<'a> Map<'a, N::TransactionID, Transaction<N>>>
+    Ledger<N, B, P, M>
+{
+    /// Writes all blocks in the ledger, from the genesis block to the latest block, to the given writer.
+    /// Each block is written as its length in bytes (as a little-endian `u32`), followed by the block bytes.
+    pub fn export_blocks<W: Write>(&self, mut writer: W) -> Result<()> {
+        for height in 0..=self.latest_height() {
+            // Serialize the block.
+            let bytes = self.get_block(height)?.to_bytes_le()?;
+            // Write the length-prefixed block.
+            u32::try_from(bytes.len())?.write_le(&mut writer)?;
+            writer.write_all(&bytes)?;
+        }
+        Ok(writer.flush()?)
+    }
+
+    /// Reads length-prefixed blocks from the given reader, as written by `export_blocks`,
+    /// adds each block as the next block in the ledger, and returns the latest block height.
+    /// Blocks that already exist in the ledger are skipped.
+    /// Note: This method stops at the first invalid block, and the blocks before it remain in the ledger.
+    pub fn import_blocks<R: Read>(&mut self, mut reader: R) -> Result<u32> {
+        loop {
+            // Read the length of the next block, or stop at the end of the reader.
+            let mut length = [0u8; 4];
+            let mut num_bytes = 0;
+            while num_bytes < length.len() {
+                match reader.read(&mut length[num_bytes..]) {
+                    Ok(0) => break,
+                    Ok(num_read) => num_bytes += num_read,
+                    Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                    Err(error) => bail!("Failed to read the next block length: {error}"),
+                }
+            }
+            // Ensure the reader did not end partway through the block length.
+            match num_bytes {
+                0 => break,
+                4 => (),
+                _ => bail!("Expected a block length of 4 bytes, found {num_bytes} bytes"),
+            }
+            let length = u32::from_le_bytes(length);
+
+            // Read the block.
+            let mut bytes = Vec::new();
+            (&mut reader).take(u64::from(length)).read_to_end(&mut bytes)?;
+            ensure!(bytes.len() == length as usize, "Expected a block of {length} bytes, found {} bytes", bytes.len());
+            let block = Block::<N>::from_bytes_le(&bytes)?;
+
+            // Skip the block if it already exists in the ledger.
+            if self.contains_block_hash(&block.hash())? {
+                debug!("Skipping block {} ('{}'), as it already exists in the ledger", block.height(), block.hash());
+                continue;
+            }
+
+            // Add the block to the ledger.
+            if let Err(error) = self.add_next_block(&block) {
+                bail!("Failed to import block {} ('{}'): {error}", block.height(), block.hash())
+            }
+        }
+        Ok(self.latest_height())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_utilities::TestRng;
+
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_export_import_blocks() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add the next two blocks.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        for _ in 0..2 {
+            let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+            ledger.add_next_block(&next_block).unwrap();
+        }
+        assert_eq!(ledger.latest_height(), 2);
+
+        // Export the blocks.
+        let mut buffer = Vec::new();
+        ledger.export_blocks(&mut buffer).unwrap();
+
+        // Ensure the blocks are imported into a new ledger.
+        let mut other_ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+        assert_eq!(other_ledger.import_blocks(&buffer[..]).unwrap(), 2);
+        assert_eq!(other_ledger.latest_hash(), ledger.latest_hash());
+        assert_eq!(other_ledger.latest_state_root(), ledger.latest_state_root());
+        // Ensure importing the same blocks again is a no-op.
+        assert_eq!(other_ledger.import_blocks(&buffer[..]).unwrap(), 2);
+
+        // Ensure the import stops at an invalid block, without modifying the ledger.
+        let mut other_ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+        let mut invalid_buffer = Vec::new();
+        for height in [0, 2] {
+            let bytes = ledger.get_block(height).unwrap().to_bytes_le().unwrap();
+            invalid_buffer.extend((bytes.len() as u32).to_le_bytes());
+            invalid_buffer.extend(bytes);
+        }
+        assert!(other_ledger.import_blocks(&invalid_buffer[..]).is_err());
+        assert_eq!(other_ledger.latest_height(), 0);
+
+        // Ensure a truncated block is rejected.
+        assert!(other_ledger.import_blocks(&buffer[..buffer.len() - 1]).is_err());
+        assert_eq!(other_ledger.latest_height(), 1);
+
+        // Ensure a truncated block length is rejected.
+        let mut other_ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+        let mut truncated_buffer = buffer.clone();
+        truncated_buffer.extend([1u8, 2]);
+        assert!(other_ledger.import_blocks(&truncated_buffer[..]).is_err());
+        assert_eq!(other_ledger.latest_height(), 2);
+    }
+}
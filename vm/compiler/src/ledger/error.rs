@@ -0,0 +1,108 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use console::{
+    network::prelude::*,
+    types::{Field, Group},
+};
+
+/// A distinguished error that may occur when checking the next block for the ledger.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockValidationError<N: Network> {
+    /// The block is well-formed, but does not build on the latest block in the ledger.
+    /// The block may be buffered, and offered again once its previous block is added.
+    Orphan { expected_previous: N::BlockHash, got_previous: N::BlockHash },
+}
+
+impl<N: Network> Display for BlockValidationError<N> {
+    /// Prints the block validation error as a string.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Orphan { expected_previous, got_previous } => write!(
+                f,
+                "The given block is an orphan block (expected previous block hash '{expected_previous}', found '{got_previous}')"
+            ),
+        }
+    }
+}
+
+impl<N: Network> std::error::Error for BlockValidationError<N> {}
+
+/// The error returned when adding a block to a paused ledger.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LedgerPaused;
+
+impl Display for LedgerPaused {
+    /// Prints the error as a string.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "The ledger is paused, and does not accept new blocks")
+    }
+}
+
+impl std::error::Error for LedgerPaused {}
+
+/// The error returned when a memory pool submission exceeds the memory pool rate limit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MemoryPoolRateLimited;
+
+impl Display for MemoryPoolRateLimited {
+    /// Prints the error as a string.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "The transaction exceeds the memory pool rate limit")
+    }
+}
+
+impl std::error::Error for MemoryPoolRateLimited {}
+
+/// The error returned when rolling back more blocks than the maximum reorg depth.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ReorgTooDeep {
+    /// The number of blocks to roll back.
+    pub depth: u32,
+    /// The maximum number of blocks that may be rolled back.
+    pub max_depth: u32,
+}
+
+impl Display for ReorgTooDeep {
+    /// Prints the error as a string.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Cannot roll back {} blocks, as the maximum reorg depth is {}", self.depth, self.max_depth)
+    }
+}
+
+impl std::error::Error for ReorgTooDeep {}
+
+/// A value in a block that already exists in the ledger.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DuplicateKind<N: Network> {
+    /// The serial number already exists in the ledger.
+    SerialNumber(Field<N>),
+    /// The commitment already exists in the ledger.
+    Commitment(Field<N>),
+    /// The nonce already exists in the ledger.
+    Nonce(Group<N>),
+}
+
+impl<N: Network> Display for DuplicateKind<N> {
+    /// Prints the duplicate as a string.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::SerialNumber(serial_number) => write!(f, "Serial number '{serial_number}'"),
+            Self::Commitment(commitment) => write!(f, "Commitment '{commitment}'"),
+            Self::Nonce(nonce) => write!(f, "Nonce '{nonce}'"),
+        }
+    }
+}
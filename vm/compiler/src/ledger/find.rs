@@ -16,9 +16,12 @@
 
 use super::*;
 
+use indexmap::IndexSet;
 use std::borrow::Cow;
 
-impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
+impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>, M: for<'a> Map<'a, N::TransactionID, Transaction<N>>>
+    Ledger<N, B, P, M>
+{
     /// Returns the record ciphertexts that belong to the given view key.
     pub fn find_record_ciphertexts<'a>(
         &'a self,
@@ -33,11 +36,37 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
             Err(e) => bail!("Failed to derive the graph key from the view key: {e}"),
         };
 
-        /// A helper method to derive the tag from the `sk_tag` and commitment.
-        fn compute_tag<N: Network>(sk_tag: Field<N>, commitment: Field<N>) -> Result<Field<N>> {
-            N::hash_psd2(&[sk_tag, commitment])
-        }
+        Ok(self.records().flat_map(move |cow| {
+            // Retrieve the commitment and record.
+            let (commitment, record) = match cow {
+                (Cow::Borrowed(commitment), record) => (*commitment, record),
+                (Cow::Owned(commitment), record) => (commitment, record),
+            };
+
+            // Ensure the record belongs to the view key, before checking the filter.
+            if !record.is_owner_with_address(&address, view_key) {
+                return None;
+            }
+
+            // Determine whether to decrypt this record (or not), based on the filter.
+            match self.filter_commitment(sk_tag, commitment, filter) {
+                Ok(Some(commitment)) => Some((commitment, record)),
+                Ok(None) => None,
+                Err(e) => {
+                    warn!("Failed to process 'find_record_ciphertexts({:?})': {e}", filter);
+                    None
+                }
+            }
+        }))
+    }
 
+    /// Returns the given commitment if its record is selected by the given filter.
+    fn filter_commitment(
+        &self,
+        sk_tag: Field<N>,
+        commitment: Field<N>,
+        filter: RecordsFilter<N>,
+    ) -> Result<Option<Field<N>>> {
         /// A helper method to derive the serial number from the private key and commitment.
         fn compute_serial_number<N: Network>(private_key: PrivateKey<N>, commitment: Field<N>) -> Result<Field<N>> {
             // Compute the generator `H` as `HashToGroup(commitment)`.
@@ -51,62 +80,41 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
             N::commit_bhp512(&(N::serial_number_domain(), commitment).to_bits_le(), &sn_nonce)
         }
 
-        Ok(self.records().flat_map(move |cow| {
-            // Retrieve the commitment and record.
-            let (commitment, record) = match cow {
-                (Cow::Borrowed(commitment), record) => (*commitment, record),
-                (Cow::Owned(commitment), record) => (commitment, record),
-            };
-
-            // Determine whether to decrypt this record (or not), based on the filter.
-            let commitment = match filter {
-                RecordsFilter::All => Ok(Some(commitment)),
-                RecordsFilter::Spent => compute_tag(sk_tag, commitment).and_then(|tag| {
+        match filter {
+            RecordsFilter::All => Ok(Some(commitment)),
+            RecordsFilter::Spent => Record::<N, Ciphertext<N>>::tag(sk_tag, commitment).and_then(|tag| {
+                // Determine if the record is spent.
+                self.contains_tag(&tag).map(|is_spent| match is_spent {
+                    true => Some(commitment),
+                    false => None,
+                })
+            }),
+            RecordsFilter::Unspent => Record::<N, Ciphertext<N>>::tag(sk_tag, commitment).and_then(|tag| {
+                // Determine if the record is spent.
+                self.contains_tag(&tag).map(|is_spent| match is_spent {
+                    true => None,
+                    false => Some(commitment),
+                })
+            }),
+            RecordsFilter::SlowSpent(private_key) => {
+                compute_serial_number(private_key, commitment).and_then(|serial_number| {
                     // Determine if the record is spent.
-                    self.contains_tag(&tag).map(|is_spent| match is_spent {
+                    self.contains_serial_number(&serial_number).map(|is_spent| match is_spent {
                         true => Some(commitment),
                         false => None,
                     })
-                }),
-                RecordsFilter::Unspent => compute_tag(sk_tag, commitment).and_then(|tag| {
+                })
+            }
+            RecordsFilter::SlowUnspent(private_key) => {
+                compute_serial_number(private_key, commitment).and_then(|serial_number| {
                     // Determine if the record is spent.
-                    self.contains_tag(&tag).map(|is_spent| match is_spent {
+                    self.contains_serial_number(&serial_number).map(|is_spent| match is_spent {
                         true => None,
                         false => Some(commitment),
                     })
-                }),
-                RecordsFilter::SlowSpent(private_key) => {
-                    compute_serial_number(private_key, commitment).and_then(|serial_number| {
-                        // Determine if the record is spent.
-                        self.contains_serial_number(&serial_number).map(|is_spent| match is_spent {
-                            true => Some(commitment),
-                            false => None,
-                        })
-                    })
-                }
-                RecordsFilter::SlowUnspent(private_key) => {
-                    compute_serial_number(private_key, commitment).and_then(|serial_number| {
-                        // Determine if the record is spent.
-                        self.contains_serial_number(&serial_number).map(|is_spent| match is_spent {
-                            true => None,
-                            false => Some(commitment),
-                        })
-                    })
-                }
-            };
-
-            match commitment {
-                Ok(Some(commitment)) => match record.is_owner(&address, view_key) {
-                    true => Some((commitment, record)),
-                    false => None,
-                },
-                Ok(None) => None,
-                Err(e) => {
-                    warn!("Failed to process 'find_record_ciphertexts({:?})': {e}", filter);
-                    None
-                }
+                })
             }
-        }))
+        }
     }
 
     /// Returns the records that belong to the given view key.
@@ -125,4 +133,250 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
             })
         })
     }
+
+    /// Returns the records that belong to the given view key, using the given thread pool to decrypt the records.
+    /// Note: This bounds the number of threads used for trial decryption to the size of the thread pool.
+    #[allow(clippy::type_complexity)]
+    pub fn scan_with_pool(
+        &self,
+        pool: &rayon::ThreadPool,
+        view_key: &ViewKey<N>,
+        filter: RecordsFilter<N>,
+    ) -> Result<Vec<(Field<N>, Record<N, Plaintext<N>>)>> {
+        // Derive the address from the view key.
+        let address = view_key.to_address();
+        // Derive the `sk_tag` from the graph key.
+        let sk_tag = match GraphKey::try_from(view_key) {
+            Ok(graph_key) => graph_key.sk_tag(),
+            Err(e) => bail!("Failed to derive the graph key from the view key: {e}"),
+        };
+
+        // Retrieve the records.
+        let records = self.records().collect::<Vec<_>>();
+
+        Ok(pool.install(|| {
+            records
+                .into_par_iter()
+                .filter_map(|(commitment, record)| {
+                    // Ensure the record belongs to the view key, before checking the filter.
+                    if !record.is_owner_with_address(&address, view_key) {
+                        return None;
+                    }
+                    // Determine whether to decrypt this record (or not), based on the filter.
+                    match self.filter_commitment(sk_tag, *commitment, filter) {
+                        Ok(Some(commitment)) => match record.decrypt(view_key) {
+                            Ok(record) => Some((commitment, record)),
+                            Err(e) => {
+                                warn!("Failed to decrypt the record: {e}");
+                                None
+                            }
+                        },
+                        Ok(None) => None,
+                        Err(e) => {
+                            warn!("Failed to process 'scan_with_pool({:?})': {e}", filter);
+                            None
+                        }
+                    }
+                })
+                .collect()
+        }))
+    }
+
+    /// Returns the record commitments in the ledger, paired with the transition that produced them.
+    /// Note: Serial numbers can not be linked to commitments without the record owner's keys,
+    /// so this set includes spent records. Use `unspent_record_set` to exclude the spent records of a view key.
+    pub fn record_set(&self) -> Result<Vec<(Field<N>, Transition<N>)>> {
+        self.commitments().map(|commitment| self.get_output_transition(*commitment)).collect()
+    }
+
+    /// Returns the unspent record commitments that belong to the given view key,
+    /// paired with the transition that produced them.
+    pub fn unspent_record_set(&self, view_key: &ViewKey<N>) -> Result<Vec<(Field<N>, Transition<N>)>> {
+        self.find_record_ciphertexts(view_key, RecordsFilter::Unspent)?
+            .map(|(commitment, _)| self.get_output_transition(commitment))
+            .collect()
+    }
+
+    /// Returns the block height and transaction ID that spent the record with the given commitment,
+    /// or `None` if the record is unspent.
+    /// Note: Spends reference serial numbers, which are only linkable to the commitment with the owner's view key.
+    pub fn find_spend(&self, view_key: &ViewKey<N>, commitment: &Field<N>) -> Result<Option<(u32, N::TransactionID)>> {
+        // Ensure the commitment exists.
+        if !self.contains_commitment(commitment)? {
+            bail!("Commitment '{commitment}' does not exist");
+        }
+        // Derive the `sk_tag` from the graph key.
+        let sk_tag = match GraphKey::try_from(view_key) {
+            Ok(graph_key) => graph_key.sk_tag(),
+            Err(e) => bail!("Failed to derive the graph key from the view key: {e}"),
+        };
+        // Compute the tag of the record.
+        let tag = Record::<N, Ciphertext<N>>::tag(sk_tag, *commitment)?;
+
+        // Find the serial number of the record, if it is spent.
+        let serial_number = match self.transitions.find_serial_number(&tag)? {
+            Some(serial_number) => serial_number,
+            None => return Ok(None),
+        };
+        // Find the transition that spent the record.
+        let transition_id = self.transitions.find_transition_id(&serial_number)?;
+        // Retrieve the block height and transaction ID of the transition.
+        self.get_transition_height(&transition_id).map(Some)
+    }
+
+    /// Returns the record commitments whose tags, under the given graph key, are in the given tags,
+    /// paired with the transition that produced them.
+    /// Note: This matches records with one hash per commitment, instead of trial decryption.
+    pub fn get_records_by_tags(
+        &self,
+        graph_key: &GraphKey<N>,
+        tags: &[Field<N>],
+    ) -> Result<Vec<(Field<N>, Transition<N>)>> {
+        // Retrieve the `sk_tag` from the graph key.
+        let sk_tag = graph_key.sk_tag();
+        // Collect the tags into a set.
+        let tags = tags.iter().collect::<IndexSet<_>>();
+
+        let mut records = Vec::new();
+        for commitment in self.commitments() {
+            // Ensure the tag of the record is in the given tags.
+            if tags.contains(&Record::<N, Ciphertext<N>>::tag(sk_tag, *commitment)?) {
+                records.push(self.get_output_transition(*commitment)?);
+            }
+        }
+        Ok(records)
+    }
+
+    /// Returns the given commitment, paired with the transition that produced it.
+    fn get_output_transition(&self, commitment: Field<N>) -> Result<(Field<N>, Transition<N>)> {
+        // Retrieve the transition ID.
+        let transition_id = self.transitions.find_transition_id(&commitment)?;
+        // Retrieve the transition.
+        match self.transitions.get_transition(&transition_id)? {
+            Some(transition) => Ok((commitment, transition)),
+            None => bail!("Missing transition '{transition_id}' for commitment '{commitment}'"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::network::Testnet3;
+    use snarkvm_utilities::TestRng;
+
+    use tracing_test::traced_test;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    #[traced_test]
+    fn test_scan_with_pool() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key and view key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        // Sample the genesis ledger.
+        let ledger = crate::ledger::test_helpers::sample_genesis_ledger(rng);
+
+        // Construct a thread pool with 2 threads.
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+        assert_eq!(pool.current_num_threads(), 2);
+
+        // Ensure the scan matches the sequential scan, for each filter.
+        for filter in [RecordsFilter::All, RecordsFilter::Unspent, RecordsFilter::Spent] {
+            let expected = ledger.find_records(&view_key, filter).unwrap().collect::<Vec<_>>();
+            let candidate = ledger.scan_with_pool(&pool, &view_key, filter).unwrap();
+            assert_eq!(candidate, expected);
+        }
+        assert!(!ledger.scan_with_pool(&pool, &view_key, RecordsFilter::All).unwrap().is_empty());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_record_set() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key and view key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Retrieve an unspent record with a non-zero balance.
+        let (spent_commitment, _) = crate::ledger::test_helpers::sample_unspent_record(&ledger, &view_key);
+        assert!(ledger.unspent_record_set(&view_key).unwrap().iter().any(|(c, _)| *c == spent_commitment));
+
+        // Spend the record.
+        let (commitment, transaction, _) = crate::ledger::test_helpers::add_split_block(&mut ledger, &private_key, rng);
+        assert_eq!(commitment, spent_commitment);
+        let fresh_commitment = *transaction.commitments().next().unwrap();
+        let transition = transaction.transitions().next().unwrap().clone();
+
+        // Ensure the record set includes every commitment.
+        let record_set = ledger.record_set().unwrap();
+        assert_eq!(record_set.len(), ledger.commitments().count());
+        assert!(record_set.iter().any(|(c, _)| *c == spent_commitment));
+        assert!(record_set.contains(&(fresh_commitment, transition.clone())));
+
+        // Ensure the spent commitment is absent, and the fresh commitment is present.
+        let unspent_record_set = ledger.unspent_record_set(&view_key).unwrap();
+        assert!(!unspent_record_set.iter().any(|(c, _)| *c == spent_commitment));
+        assert!(unspent_record_set.contains(&(fresh_commitment, transition)));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_find_spend() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key and view key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Retrieve an unspent record with a non-zero balance, and ensure it is not spent.
+        let (commitment, _) = crate::ledger::test_helpers::sample_unspent_record(&ledger, &view_key);
+        assert_eq!(ledger.find_spend(&view_key, &commitment).unwrap(), None);
+
+        // Spend the record.
+        let (spent_commitment, transaction, _) =
+            crate::ledger::test_helpers::add_split_block(&mut ledger, &private_key, rng);
+        assert_eq!(spent_commitment, commitment);
+
+        // Ensure the spending transaction is found by the commitment.
+        assert_eq!(ledger.find_spend(&view_key, &commitment).unwrap(), Some((1, transaction.id())));
+
+        // Ensure an unknown commitment is rejected.
+        assert!(ledger.find_spend(&view_key, &Field::from_u64(1234)).is_err());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_get_records_by_tags() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key, view key, and graph key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        let graph_key = GraphKey::try_from(view_key).unwrap();
+        // Sample the genesis ledger.
+        let ledger = crate::ledger::test_helpers::sample_genesis_ledger(rng);
+
+        // Compute the tag of a record that belongs to the view key.
+        let (commitment, _) = ledger.find_record_ciphertexts(&view_key, RecordsFilter::All).unwrap().next().unwrap();
+        let tag = Record::<CurrentNetwork, Ciphertext<CurrentNetwork>>::tag(graph_key.sk_tag(), commitment).unwrap();
+
+        // Ensure the record is retrieved by its tag.
+        let records = ledger.get_records_by_tags(&graph_key, &[tag, Field::from_u64(1234)]).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].0, commitment);
+        assert!(records[0].1.output_records().any(|(c, _)| *c == commitment));
+
+        // Ensure no records are retrieved for an unknown tag.
+        assert!(ledger.get_records_by_tags(&graph_key, &[Field::from_u64(1234)]).unwrap().is_empty());
+        assert!(ledger.get_records_by_tags(&graph_key, &[]).unwrap().is_empty());
+    }
 }
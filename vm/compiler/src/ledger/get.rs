@@ -16,7 +16,12 @@
 
 use super::*;
 
-impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
+use console::program::Owner;
+use core::cmp::Ordering;
+
+impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>, M: for<'a> Map<'a, N::TransactionID, Transaction<N>>>
+    Ledger<N, B, P, M>
+{
     /// Returns the block for the given block height.
     pub fn get_block(&self, height: u32) -> Result<Block<N>> {
         // Retrieve the block hash.
@@ -39,6 +44,41 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
         }
     }
 
+    /// Returns the block hash for the given block height, without retrieving the block.
+    /// Note: The latest block hash is returned from memory, and other block hashes are read from the height index.
+    pub fn get_block_hash(&self, height: u32) -> Result<N::BlockHash> {
+        match height.cmp(&self.current_height) {
+            Ordering::Less => self.get_hash(height),
+            Ordering::Equal => Ok(self.current_hash),
+            Ordering::Greater => bail!("Block {height} does not exist in storage"),
+        }
+    }
+
+    /// Returns a block locator, as the block hashes at the latest height, and at exponentially spaced heights below it,
+    /// i.e. `latest`, `latest - 1`, `latest - 2`, `latest - 4`, and so on, ending with the genesis block.
+    pub fn block_locator(&self) -> Result<Vec<(u32, N::BlockHash)>> {
+        Self::block_locator_heights(self.latest_height())
+            .into_iter()
+            .map(|height| Ok((height, self.get_block_hash(height)?)))
+            .collect()
+    }
+
+    /// Returns the heights of a block locator for the given latest height, in decreasing order.
+    fn block_locator_heights(latest_height: u32) -> Vec<u32> {
+        let mut heights = vec![latest_height];
+        // Add the heights at offsets from the latest height that are powers of two.
+        let mut offset = Some(1u32);
+        while let Some(height) = offset.and_then(|offset| latest_height.checked_sub(offset)) {
+            heights.push(height);
+            offset = offset.and_then(|offset| offset.checked_mul(2));
+        }
+        // Ensure the locator ends with the genesis block.
+        if heights.last() != Some(&0) {
+            heights.push(0);
+        }
+        heights
+    }
+
     /// Returns the previous block hash for the given block height.
     pub fn get_previous_hash(&self, height: u32) -> Result<N::BlockHash> {
         match self.blocks.get_previous_block_hash(height)? {
@@ -61,6 +101,67 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
         }
     }
 
+    /// Returns the `(block height, coinbase target, proof target)` of each block in the range `start..end`,
+    /// read from the stored block headers.
+    pub fn target_history(&self, start: u32, end: u32) -> Result<Vec<(u32, u64, u64)>> {
+        // Ensure the range is valid.
+        ensure!(start <= end, "Invalid block range {start}..{end}");
+        ensure!(end <= self.latest_height().saturating_add(1), "Block {} does not exist in storage", end - 1);
+        // Retrieve the targets from the block headers.
+        Ok(self
+            .get_headers_in_range(start, end)?
+            .into_iter()
+            .map(|(height, header)| (height, header.coinbase_target(), header.proof_target()))
+            .collect())
+    }
+
+    /// Returns the root of a Merkle tree over the transactions roots of each block in the range `start..end`,
+    /// as a single digest of the transactions in the range.
+    pub fn transactions_root_for_range(&self, start: u32, end: u32) -> Result<Field<N>> {
+        // Ensure the range is valid.
+        ensure!(start <= end, "Invalid block range {start}..{end}");
+        ensure!(end <= self.latest_height().saturating_add(1), "Block {} does not exist in storage", end - 1);
+        // Prepare the leaves from the block headers.
+        let leaves = self
+            .get_headers_in_range(start, end)?
+            .into_iter()
+            .map(|(_, header)| header.transactions_root().to_bits_le())
+            .collect::<Vec<_>>();
+        // Compute the root.
+        Ok(*N::merkle_tree_bhp::<BLOCKS_DEPTH>(&leaves)?.root())
+    }
+
+    /// Returns the `(block height, block header)` of each block in the range `start..end`, in ascending order of height.
+    fn get_headers_in_range(&self, start: u32, end: u32) -> Result<Vec<(u32, Header<N>)>> {
+        let headers = self
+            .blocks
+            .get_block_hashes(start, end)
+            .map(|(height, block_hash)| match self.blocks.get_block_header(&block_hash)? {
+                Some(header) => Ok((height, header)),
+                None => bail!("Missing block header for block {height}"),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        // Ensure every block in the range exists.
+        ensure!(headers.len() == (end - start) as usize, "Missing a block in the range {start}..{end}");
+        Ok(headers)
+    }
+
+    /// Returns the height of the latest block with a timestamp at or before the given timestamp.
+    /// If the given timestamp is before the genesis block, the genesis block height is returned.
+    pub fn height_for_timestamp(&self, timestamp: i64) -> Result<u32> {
+        // Binary search the block headers, as the block timestamps are increasing.
+        let (mut low, mut high) = (0, self.latest_height());
+        while low < high {
+            // Round up, so that the search progresses when `low` is updated.
+            let middle = low + (high - low).div_ceil(2);
+            match self.get_header(middle)?.timestamp() <= timestamp {
+                true => low = middle,
+                false => high = middle - 1,
+            }
+        }
+        Ok(low)
+    }
+
     /// Returns the block transactions for the given block height.
     pub fn get_transactions(&self, height: u32) -> Result<Transactions<N>> {
         // Retrieve the block hash.
@@ -75,6 +176,114 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
         }
     }
 
+    /// Returns the block transactions for the given block hash.
+    pub fn get_transactions_by_hash(&self, block_hash: &N::BlockHash) -> Result<Transactions<N>> {
+        // Ensure the block hash exists.
+        if !self.contains_block_hash(block_hash)? {
+            bail!("Block '{block_hash}' does not exist in storage")
+        }
+        // Retrieve the block transactions.
+        match self.blocks.get_block_transactions(block_hash)? {
+            Some(transactions) => Ok(transactions),
+            None => bail!("Missing block transactions for block '{block_hash}'"),
+        }
+    }
+
+    /// Returns the transition public keys for the given block height, in the order of the block transitions.
+    /// Note: The keys are read from the stored transitions, without retrieving the block transactions.
+    pub fn get_transition_public_keys(&self, height: u32) -> Result<Vec<Group<N>>> {
+        // Retrieve the block hash.
+        let block_hash = match self.blocks.get_block_hash(height)? {
+            Some(block_hash) => block_hash,
+            None => bail!("Block {height} does not exist in storage"),
+        };
+        // Retrieve the block transaction IDs.
+        let transaction_ids = match self.blocks.get_block_transaction_ids(&block_hash)? {
+            Some(transaction_ids) => transaction_ids,
+            None => bail!("Missing block transactions for block {height}"),
+        };
+
+        let mut tpks = Vec::new();
+        for transaction_id in &transaction_ids {
+            // Retrieve the transition IDs of the transaction.
+            let transition_ids = match self.transactions.get_transition_ids(transaction_id)? {
+                Some(transition_ids) => transition_ids,
+                None => bail!("Missing transaction '{transaction_id}' in block {height}"),
+            };
+            // Retrieve the transition public keys.
+            for transition_id in &transition_ids {
+                match self.transitions.get_tpk(transition_id)? {
+                    Some(tpk) => tpks.push(tpk),
+                    None => bail!("Missing transition public key for transition '{transition_id}'"),
+                }
+            }
+        }
+        Ok(tpks)
+    }
+
+    /// Returns the owner addresses of the publicly-owned output records in the given block height, in order of first appearance.
+    /// Note: The owners of privately-owned output records are hidden, and are skipped.
+    pub fn recipients_in_block(&self, height: u32) -> Result<Vec<Address<N>>> {
+        // Retrieve the block transactions.
+        let transactions = self.get_transactions(height)?;
+        // Retrieve the public owners of the output records.
+        let recipients = transactions
+            .transitions()
+            .flat_map(Transition::output_records)
+            .filter_map(|(_, record)| match record.owner() {
+                Owner::Public(owner) => Some(*owner),
+                Owner::Private(..) => None,
+            })
+            .collect::<IndexSet<_>>();
+        Ok(recipients.into_iter().collect())
+    }
+
+    /// Returns the sum of the public balances of the output records in the given block height.
+    /// Note: The balances of output records with a private balance are hidden, and are skipped.
+    pub fn public_balance(&self, height: u32) -> Result<u64> {
+        // Retrieve the block transactions.
+        let transactions = self.get_transactions(height)?;
+        // Sum the public balances of the output records.
+        Self::sum_public_balances(
+            transactions.transitions().flat_map(Transition::output_records).map(|(_, record)| record),
+        )
+    }
+
+    /// Returns the sum of the public balances of the given records, skipping the records with a private balance.
+    fn sum_public_balances<'a>(records: impl IntoIterator<Item = &'a Record<N, Ciphertext<N>>>) -> Result<u64> {
+        let mut balance = 0u64;
+        for record in records {
+            if let Balance::Public(gates) = record.gates() {
+                balance = match balance.checked_add(**gates) {
+                    Some(balance) => balance,
+                    None => bail!("The sum of the public balances overflows"),
+                };
+            }
+        }
+        Ok(balance)
+    }
+
+    /// Returns the coinbase transaction for the given block height, if one exists.
+    pub fn get_coinbase_transaction(&self, height: u32) -> Result<Option<Transaction<N>>> {
+        // Retrieve the block transactions.
+        let transactions = self.get_transactions(height)?;
+        // Return the first transaction that is a coinbase transaction.
+        Ok(transactions.into_transactions().find(Transaction::is_coinbase))
+    }
+
+    /// Returns a page of up to `limit` of the `(block height, transaction ID)` pairs for the given address,
+    /// in order of block height, after skipping the first `offset` pairs.
+    /// Note: Only transactions that output a record publicly owned by the address are indexed,
+    /// as the owners of private records are not known to the ledger.
+    pub fn get_transactions_for_public_owner_paginated(
+        &self,
+        address: &Address<N>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<(u32, N::TransactionID)>> {
+        self.blocks.find_transaction_ids_for_public_owner(address, offset, limit)
+    }
+
     /// Returns the transaction for the given transaction id.
     pub fn get_transaction(&self, transaction_id: N::TransactionID) -> Result<Transaction<N>> {
         // Retrieve the transaction.
@@ -84,6 +293,75 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
         }
     }
 
+    /// Returns the number of confirmations for the given transaction ID, counting the block that contains it,
+    /// or `None` if the transaction is not in the ledger.
+    pub fn confirmations(&self, transaction_id: &N::TransactionID) -> Result<Option<u32>> {
+        // Find the block that contains the transaction.
+        let block_hash = match self.blocks.find_block_hash(transaction_id)? {
+            Some(block_hash) => block_hash,
+            None => return Ok(None),
+        };
+        // Retrieve the block height.
+        match self.blocks.get_block_height(&block_hash)? {
+            Some(height) => Ok(Some(self.latest_height().saturating_sub(height) + 1)),
+            None => bail!("Missing block height for block '{block_hash}'"),
+        }
+    }
+
+    /// Returns the height of the block that contains the given commitment.
+    /// Note: This follows the commitment, transition, transaction, and block indexes in storage,
+    /// so each step is a map lookup, and no separate commitment-to-height map needs to be kept in sync.
+    pub fn get_commitment_height(&self, commitment: &Field<N>) -> Result<u32> {
+        // Ensure the commitment exists.
+        if !self.contains_commitment(commitment)? {
+            bail!("Commitment '{commitment}' does not exist");
+        }
+        // Find the transition that contains the commitment.
+        let transition_id = self.transitions.find_transition_id(commitment)?;
+        // Retrieve the block height of the transition.
+        self.get_transition_height(&transition_id).map(|(height, _)| height)
+    }
+
+    /// Returns the height of the block, and the ID of the transaction, that contain the given transition.
+    pub(super) fn get_transition_height(&self, transition_id: &N::TransitionID) -> Result<(u32, N::TransactionID)> {
+        // Find the transaction that contains the transition.
+        let transaction_id = match self.transactions.find_transaction_id(transition_id)? {
+            Some(transaction_id) => transaction_id,
+            None => bail!("The transaction ID for transition '{transition_id}' is not in the ledger"),
+        };
+        // Find the block that contains the transaction.
+        let block_hash = match self.blocks.find_block_hash(&transaction_id)? {
+            Some(block_hash) => block_hash,
+            None => bail!("The block hash for transaction '{transaction_id}' is not in the ledger"),
+        };
+        // Retrieve the block height.
+        match self.blocks.get_block_height(&block_hash)? {
+            Some(height) => Ok((height, transaction_id)),
+            None => bail!("Missing block height for block '{block_hash}'"),
+        }
+    }
+
+    /// Returns the decrypted record outputs of the given transition, that belong to the given view key.
+    pub fn get_decrypted_outputs(
+        &self,
+        transition_id: &N::TransitionID,
+        view_key: &ViewKey<N>,
+    ) -> Result<Vec<Record<N, Plaintext<N>>>> {
+        // Retrieve the transition.
+        let transition = match self.transitions.get_transition(transition_id)? {
+            Some(transition) => transition,
+            None => bail!("Missing transition for id {transition_id}"),
+        };
+        // Derive the address from the view key.
+        let address = view_key.to_address();
+        // Decrypt the record outputs that belong to the view key.
+        transition
+            .output_records()
+            .filter(|(_, record)| record.is_owner_with_address(&address, view_key))
+            .map(|(_, record)| record.decrypt(view_key))
+            .collect()
+    }
+
     /// Returns the program for the given program id.
     pub fn get_program(&self, program_id: ProgramID<N>) -> Result<Program<N>> {
         match self.transactions.get_program(&program_id)? {
@@ -92,6 +370,30 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
         }
     }
 
+    /// Returns the program ID and deployment height, for all programs deployed in the ledger.
+    pub fn deployed_programs(&self) -> Result<Vec<(ProgramID<N>, u32)>> {
+        self.transactions
+            .program_ids()
+            .map(|program_id| {
+                // Retrieve the deployment transaction ID.
+                let transaction_id = match self.transactions.find_deployment_id(&program_id)? {
+                    Some(transaction_id) => transaction_id,
+                    None => bail!("Missing deployment for program '{program_id}'"),
+                };
+                // Retrieve the block hash of the deployment.
+                let block_hash = match self.blocks.find_block_hash(&transaction_id)? {
+                    Some(block_hash) => block_hash,
+                    None => bail!("Missing block for deployment transaction '{transaction_id}'"),
+                };
+                // Retrieve the block height of the deployment.
+                match self.blocks.get_block_height(&block_hash)? {
+                    Some(height) => Ok((program_id.into_owned(), height)),
+                    None => bail!("Missing block height for block '{block_hash}'"),
+                }
+            })
+            .collect()
+    }
+
     /// Returns the block signature for the given block height.
     pub fn get_signature(&self, height: u32) -> Result<Signature<N>> {
         // Retrieve the block hash.
@@ -99,18 +401,37 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
             Some(block_hash) => block_hash,
             None => bail!("Block {height} does not exist in storage"),
         };
-        // Retrieve the block transaction.
+        // Retrieve the block signature.
         match self.blocks.get_block_signature(&block_hash)? {
             Some(signature) => Ok(signature),
             None => bail!("Missing signature for block {height}"),
         }
     }
+
+    /// Returns the address of the signer for the given block height.
+    pub fn block_signer(&self, height: u32) -> Result<Address<N>> {
+        // Retrieve the block hash and signature.
+        let block_hash = self.get_hash(height)?;
+        let signature = self.get_signature(height)?;
+        // Derive the signer address.
+        let signer = signature.to_address();
+        // Ensure the signature is valid for the block hash.
+        ensure!(signature.verify(&signer, &[*block_hash]), "Invalid signature for block {height} ('{block_hash}')");
+        Ok(signer)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::ledger::test_helpers::CurrentLedger;
+    use console::{
+        network::Testnet3,
+        program::{Literal, Value},
+        types::U64,
+    };
+
+    type CurrentNetwork = Testnet3;
 
     #[test]
     fn test_get_block() {
@@ -124,4 +445,651 @@ mod tests {
         // Ensure the genesis block matches.
         assert_eq!(genesis, candidate);
     }
+
+    #[test]
+    fn test_target_history() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add the next block.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Ensure the history matches the targets of each block header.
+        let history = ledger.target_history(0, 2).unwrap();
+        assert_eq!(history.len(), 2);
+        for (height, coinbase_target, proof_target) in history {
+            let header = ledger.get_header(height).unwrap();
+            assert_eq!((coinbase_target, proof_target), (header.coinbase_target(), header.proof_target()));
+        }
+        assert_eq!(ledger.target_history(1, 2).unwrap().len(), 1);
+        assert!(ledger.target_history(1, 1).unwrap().is_empty());
+
+        // Ensure an invalid range fails.
+        assert!(ledger.target_history(2, 1).is_err());
+        assert!(ledger.target_history(0, 3).is_err());
+    }
+
+    #[test]
+    fn test_get_coinbase_transaction() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key and view key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        let address = Address::try_from(&private_key).unwrap();
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Retrieve the coinbase transaction.
+        let coinbase = ledger.get_coinbase_transaction(0).unwrap().unwrap();
+        assert!(coinbase.is_coinbase());
+        // Ensure the coinbase transaction is in the genesis block.
+        assert!(ledger.get_block(0).unwrap().transactions().contains_key(&coinbase.id()));
+
+        // Ensure the coinbase output amount matches the genesis supply.
+        let gates = coinbase
+            .transitions()
+            .flat_map(Transition::output_records)
+            .map(|(_, record)| ***record.decrypt(&view_key).unwrap().gates())
+            .sum::<u64>();
+        assert_eq!(gates, 1_100_000_000_000_000);
+
+        // Add a block with a mint.
+        let mint = Transaction::execute(
+            ledger.vm(),
+            &private_key,
+            &ProgramID::from_str("credits.aleo").unwrap(),
+            Identifier::from_str("mint").unwrap(),
+            &[Value::from_str(&address.to_string()).unwrap(), Value::from_str("100u64").unwrap()],
+            None,
+            rng,
+        )
+        .unwrap();
+        assert!(mint.is_coinbase());
+        ledger.add_to_memory_pool(mint.clone()).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Ensure the mint is the coinbase transaction of the block.
+        assert_eq!(ledger.get_coinbase_transaction(next_block.height()).unwrap(), Some(mint));
+
+        // Ensure a block without a mint has no coinbase transaction.
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        assert_eq!(ledger.get_coinbase_transaction(next_block.height()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_get_transactions_by_hash() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add a block with a transaction.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction.clone()).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Ensure the transactions by block hash match the transactions by block height.
+        for height in 0..=ledger.latest_height() {
+            let transactions = ledger.get_transactions_by_hash(&ledger.get_hash(height).unwrap()).unwrap();
+            assert_eq!(transactions, ledger.get_transactions(height).unwrap());
+        }
+        let transactions = ledger.get_transactions_by_hash(&next_block.hash()).unwrap();
+        assert!(transactions.contains_key(&transaction.id()));
+
+        // Ensure an unknown block hash is rejected.
+        assert!(ledger.get_transactions_by_hash(&Default::default()).is_err());
+    }
+
+    #[test]
+    fn test_get_transition_public_keys() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add a block with an execution and a deployment transaction.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let transaction = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Ensure the transition public keys match the block, for each height.
+        for height in 0..=ledger.latest_height() {
+            let expected = ledger.get_block(height).unwrap().transition_public_keys().copied().collect::<Vec<_>>();
+            assert!(!expected.is_empty());
+            assert_eq!(ledger.get_transition_public_keys(height).unwrap(), expected);
+        }
+
+        // Ensure a missing block height fails.
+        assert!(ledger.get_transition_public_keys(ledger.latest_height() + 1).is_err());
+    }
+
+    #[test]
+    fn test_recipients_in_block() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+        // Sample a recipient address.
+        let recipient = Address::try_from(&PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+
+        // Deploy a program with a publicly-owned record.
+        let program =
+            crate::ledger::test_helpers::deploy_public_token_program(&mut ledger, &private_key, "public_transfer", rng);
+
+        // Transfer tokens to the recipient.
+        let transaction = crate::ledger::test_helpers::sample_public_mint_transaction(
+            &ledger,
+            &private_key,
+            &program,
+            recipient,
+            5,
+            rng,
+        );
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Ensure the recipient is listed for the transfer block only.
+        assert_eq!(ledger.recipients_in_block(next_block.height()).unwrap(), vec![recipient]);
+        assert!(!ledger.recipients_in_block(next_block.height() - 1).unwrap().contains(&recipient));
+        // Ensure a missing block height fails.
+        assert!(ledger.recipients_in_block(next_block.height() + 1).is_err());
+    }
+
+    #[test]
+    fn test_sum_public_balances() {
+        let rng = &mut TestRng::default();
+
+        // Sample an address.
+        let address = Address::try_from(&PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+
+        // Samples an encrypted record with the given owner and balance.
+        let mut sample_record = |owner, gates| {
+            let randomizer = Uniform::rand(rng);
+            let nonce = CurrentNetwork::g_scalar_multiply(&randomizer);
+            let record = Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::from_plaintext(
+                owner,
+                gates,
+                IndexMap::new(),
+                nonce,
+            )
+            .unwrap();
+            record.encrypt(randomizer).unwrap()
+        };
+        let public_owner = || Owner::Public(address);
+        let private_owner = || Owner::Private(Plaintext::from(Literal::Address(address)));
+        let public_gates = |gates| Balance::Public(U64::new(gates));
+        let private_gates = |gates| Balance::Private(Plaintext::from(Literal::U64(U64::new(gates))));
+
+        // Ensure only the public balances are summed, regardless of the owner visibility.
+        let records = [
+            sample_record(public_owner(), public_gates(5)),
+            sample_record(private_owner(), public_gates(7)),
+            sample_record(public_owner(), private_gates(11)),
+            sample_record(private_owner(), private_gates(13)),
+        ];
+        assert_eq!(CurrentLedger::sum_public_balances(&records).unwrap(), 12);
+        assert_eq!(CurrentLedger::sum_public_balances(&records[2..]).unwrap(), 0);
+        assert_eq!(CurrentLedger::sum_public_balances([]).unwrap(), 0);
+
+        // Ensure an overflowing sum fails.
+        // Note: A record with such a large balance cannot be encrypted, so the records are constructed directly.
+        let nonce = Uniform::rand(rng);
+        let records = [u64::MAX, 1].map(|gates| {
+            Record::<CurrentNetwork, Ciphertext<CurrentNetwork>>::from_ciphertext(
+                Owner::Public(address),
+                Balance::Public(U64::new(gates)),
+                IndexMap::new(),
+                nonce,
+            )
+            .unwrap()
+        });
+        assert!(CurrentLedger::sum_public_balances(&records).is_err());
+    }
+
+    #[test]
+    fn test_public_balance() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key and address.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        let address = Address::try_from(&private_key).unwrap();
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Ensure the genesis block, whose records have private balances, has no public balance.
+        assert!(ledger.get_block(0).unwrap().transitions().flat_map(Transition::output_records).next().is_some());
+        assert_eq!(ledger.public_balance(0).unwrap(), 0);
+
+        // Deploy a program with a record with a public balance.
+        let program =
+            crate::ledger::test_helpers::deploy_public_token_program(&mut ledger, &private_key, "public_balance", rng);
+
+        // Mint a token with a public balance, in a block.
+        let transaction = crate::ledger::test_helpers::sample_public_mint_transaction(
+            &ledger,
+            &private_key,
+            &program,
+            address,
+            1,
+            rng,
+        );
+        ledger.set_dust_threshold(0);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Ensure the public balance matches the sum of the visible balances in the block.
+        let expected = next_block
+            .transitions()
+            .flat_map(Transition::output_records)
+            .filter_map(|(_, record)| match record.gates() {
+                Balance::Public(gates) => Some(**gates),
+                Balance::Private(..) => None,
+            })
+            .sum::<u64>();
+        assert_eq!(ledger.public_balance(next_block.height()).unwrap(), expected);
+        // Ensure a missing block height fails.
+        assert!(ledger.public_balance(next_block.height() + 1).is_err());
+    }
+
+    #[test]
+    fn test_get_transactions_for_public_owner_paginated() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key, view key, and address.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        let address = Address::try_from(&view_key).unwrap();
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Deploy a program with a publicly-owned record.
+        let program =
+            crate::ledger::test_helpers::deploy_public_token_program(&mut ledger, &private_key, "public_token", rng);
+
+        // Mint the tokens over a few blocks.
+        let mut expected = Vec::new();
+        for _ in 0..3 {
+            for amount in 0..2 {
+                let transaction = crate::ledger::test_helpers::sample_public_mint_transaction(
+                    &ledger,
+                    &private_key,
+                    &program,
+                    address,
+                    amount,
+                    rng,
+                );
+                ledger.add_to_memory_pool(transaction).unwrap();
+            }
+            let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+            ledger.add_next_block(&next_block).unwrap();
+            expected.extend(next_block.transaction_ids().map(|id| (next_block.height(), *id)));
+        }
+        assert_eq!(expected.len(), 6);
+
+        // Ensure all of the transactions are found.
+        assert_eq!(ledger.get_transactions_for_public_owner_paginated(&address, 0, usize::MAX).unwrap(), expected);
+
+        // Ensure consecutive pages cover the full result set, without overlap.
+        let mut candidate = Vec::new();
+        for offset in (0..expected.len() + 4).step_by(4) {
+            let page = ledger.get_transactions_for_public_owner_paginated(&address, offset, 4).unwrap();
+            assert!(page.len() <= 4);
+            candidate.extend(page);
+        }
+        assert_eq!(candidate, expected);
+
+        // Ensure an unknown address has no transactions.
+        let other_address = Address::try_from(&PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        assert!(ledger.get_transactions_for_public_owner_paginated(&other_address, 0, 4).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_decrypted_outputs() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key and view key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Sample the recipient.
+        let recipient_private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let recipient_view_key = ViewKey::try_from(recipient_private_key).unwrap();
+        let recipient = Address::try_from(&recipient_view_key).unwrap();
+
+        // Transfer credits to the recipient.
+        let (_, record) = ledger.find_records(&view_key, RecordsFilter::Unspent).unwrap().next().unwrap();
+        let transaction = Transaction::execute(
+            ledger.vm(),
+            &private_key,
+            &ProgramID::from_str("credits.aleo").unwrap(),
+            Identifier::from_str("transfer").unwrap(),
+            &[
+                Value::Record(record),
+                Value::from_str(&recipient.to_string()).unwrap(),
+                Value::from_str("5u64").unwrap(),
+            ],
+            None,
+            rng,
+        )
+        .unwrap();
+        ledger.add_to_memory_pool(transaction.clone()).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Retrieve the transfer transition.
+        let transition_id = transaction.transition_ids().next().unwrap();
+
+        // Ensure the recipient can decrypt their output.
+        let outputs = ledger.get_decrypted_outputs(transition_id, &recipient_view_key).unwrap();
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(**outputs[0].owner(), recipient);
+        assert_eq!(***outputs[0].gates(), 5);
+
+        // Ensure the sender can only decrypt their change output.
+        let outputs = ledger.get_decrypted_outputs(transition_id, &view_key).unwrap();
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(**outputs[0].owner(), view_key.to_address());
+
+        // Ensure an unknown transition is rejected.
+        let transition_id = Field::<CurrentNetwork>::from_u64(1234).into();
+        assert!(ledger.get_decrypted_outputs(&transition_id, &view_key).is_err());
+    }
+
+    #[test]
+    fn test_get_signature() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Propose and add the next block.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Ensure the signatures match the signatures of the blocks.
+        for height in 0..=ledger.latest_height() {
+            assert_eq!(ledger.get_signature(height).unwrap(), *ledger.get_block(height).unwrap().signature());
+        }
+        assert_eq!(ledger.get_signature(1).unwrap(), *next_block.signature());
+
+        // Ensure the signature of a missing block is not found.
+        assert!(ledger.get_signature(2).is_err());
+    }
+
+    #[test]
+    fn test_block_signer() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        let address = Address::try_from(&private_key).unwrap();
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Ensure the signer of the genesis block is the genesis address.
+        assert_eq!(ledger.block_signer(0).unwrap(), address);
+
+        // Propose and add the next block.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Ensure the signer of the next block is the proposer.
+        assert_eq!(ledger.block_signer(1).unwrap(), address);
+        assert_eq!(ledger.block_signer(1).unwrap(), next_block.signature().to_address());
+
+        // Ensure the signer of a missing block is not found.
+        assert!(ledger.block_signer(2).is_err());
+    }
+
+    #[test]
+    fn test_deployed_programs() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Ensure no programs are deployed in the genesis ledger.
+        assert!(ledger.deployed_programs().unwrap().is_empty());
+
+        // Deploy a program in the next block.
+        let transaction = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Ensure the program is listed with its deployment height.
+        let program_id = *crate::ledger::vm::test_helpers::sample_program().id();
+        assert_eq!(ledger.deployed_programs().unwrap(), vec![(program_id, 1)]);
+    }
+
+    #[test]
+    fn test_get_block_hash() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add the next two blocks.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        for _ in 0..2 {
+            let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+            ledger.add_next_block(&next_block).unwrap();
+        }
+        assert_eq!(ledger.latest_height(), 2);
+
+        // Ensure the block hashes match the blocks.
+        for height in 0..=ledger.latest_height() {
+            assert_eq!(ledger.get_block_hash(height).unwrap(), ledger.get_block(height).unwrap().hash());
+        }
+        // Ensure a block hash beyond the latest height does not exist.
+        assert!(ledger.get_block_hash(ledger.latest_height() + 1).is_err());
+    }
+
+    #[test]
+    fn test_block_locator_heights() {
+        // Ensure the offsets from the latest height are powers of two, ending with the genesis block.
+        assert_eq!(CurrentLedger::block_locator_heights(0), vec![0]);
+        assert_eq!(CurrentLedger::block_locator_heights(1), vec![1, 0]);
+        assert_eq!(CurrentLedger::block_locator_heights(2), vec![2, 1, 0]);
+        assert_eq!(CurrentLedger::block_locator_heights(4), vec![4, 3, 2, 0]);
+        assert_eq!(CurrentLedger::block_locator_heights(20), vec![20, 19, 18, 16, 12, 4, 0]);
+
+        // Ensure the locator is logarithmic in the latest height.
+        let heights = CurrentLedger::block_locator_heights(u32::MAX);
+        assert_eq!(heights.len(), 34);
+        assert_eq!(heights.first(), Some(&u32::MAX));
+        assert_eq!(heights.last(), Some(&0));
+    }
+
+    #[test]
+    fn test_block_locator() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+        assert_eq!(ledger.block_locator().unwrap(), vec![(0, ledger.latest_hash())]);
+
+        // Add the next blocks.
+        for _ in 0..10 {
+            let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+            ledger.add_next_block(&next_block).unwrap();
+        }
+
+        // Ensure the locator includes the latest block and the genesis block.
+        let locator = ledger.block_locator().unwrap();
+        assert_eq!(locator.first(), Some(&(10, ledger.latest_hash())));
+        assert_eq!(locator.last(), Some(&(0, ledger.get_hash(0).unwrap())));
+        // Ensure the gaps between the heights increase exponentially.
+        let heights = locator.iter().map(|(height, _)| *height).collect::<Vec<_>>();
+        assert_eq!(heights, vec![10, 9, 8, 6, 2, 0]);
+        // Ensure the block hashes match the blocks.
+        for (height, block_hash) in locator {
+            assert_eq!(block_hash, ledger.get_block(height).unwrap().hash());
+        }
+    }
+
+    #[test]
+    fn test_transactions_root_for_range() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample two genesis ledgers.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+        let mut other = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add an empty block to the first ledger, and a block with a transaction to the second ledger.
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        other.add_to_memory_pool(transaction).unwrap();
+        let next_block = other.propose_next_block(&private_key, rng).unwrap();
+        other.add_next_block(&next_block).unwrap();
+
+        // Ensure the digest is deterministic.
+        let root = ledger.transactions_root_for_range(0, 2).unwrap();
+        assert_eq!(root, ledger.transactions_root_for_range(0, 2).unwrap());
+        // Ensure the digest matches for a range of identical blocks.
+        assert_eq!(ledger.transactions_root_for_range(0, 1).unwrap(), other.transactions_root_for_range(0, 1).unwrap());
+        // Ensure the digest changes when a block in the range changes.
+        assert_ne!(root, other.transactions_root_for_range(0, 2).unwrap());
+        // Ensure the digest changes with the range.
+        assert_ne!(root, ledger.transactions_root_for_range(0, 1).unwrap());
+        assert_ne!(root, ledger.transactions_root_for_range(1, 2).unwrap());
+
+        // Ensure an invalid range is rejected.
+        assert!(ledger.transactions_root_for_range(1, 0).is_err());
+        assert!(ledger.transactions_root_for_range(0, 3).is_err());
+    }
+
+    #[test]
+    fn test_height_for_timestamp() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add the next blocks.
+        for _ in 0..5 {
+            let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+            ledger.add_next_block(&next_block).unwrap();
+        }
+
+        // Ensure each block timestamp maps to its block, and the preceding second maps to the previous block.
+        for height in 0..=ledger.latest_height() {
+            let timestamp = ledger.get_header(height).unwrap().timestamp();
+            assert_eq!(ledger.height_for_timestamp(timestamp).unwrap(), height);
+            assert_eq!(ledger.height_for_timestamp(timestamp - 1).unwrap(), height.saturating_sub(1));
+        }
+
+        // Ensure timestamps before the genesis block map to the genesis block.
+        assert_eq!(ledger.height_for_timestamp(i64::MIN).unwrap(), 0);
+        // Ensure timestamps after the latest block map to the latest block.
+        assert_eq!(ledger.height_for_timestamp(ledger.latest_timestamp() + 1).unwrap(), 5);
+        assert_eq!(ledger.height_for_timestamp(i64::MAX).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_get_commitment_height() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add the next block.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Ensure the commitment heights match a scan of the blocks.
+        let mut num_commitments = 0;
+        for height in 0..=ledger.latest_height() {
+            let block = ledger.get_block(height).unwrap();
+            for commitment in block.transactions().values().flat_map(Transaction::commitments) {
+                assert!(ledger.contains_commitment(commitment).unwrap());
+                assert_eq!(ledger.get_commitment_height(commitment).unwrap(), height);
+                num_commitments += 1;
+            }
+        }
+        assert_eq!(ledger.commitments().count(), num_commitments);
+
+        // Ensure a missing commitment is not found.
+        let commitment = Field::rand(rng);
+        assert!(!ledger.contains_commitment(&commitment).unwrap());
+        assert!(ledger.get_commitment_height(&commitment).is_err());
+    }
+
+    #[test]
+    fn test_confirmations() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Ensure the coinbase transaction of the genesis block has one confirmation.
+        let coinbase = ledger.get_coinbase_transaction(0).unwrap().unwrap();
+        assert_eq!(ledger.confirmations(&coinbase.id()).unwrap(), Some(1));
+
+        // Ensure a transaction that is not in the ledger has no confirmations.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        let transaction_id = transaction.id();
+        assert_eq!(ledger.confirmations(&transaction_id).unwrap(), None);
+
+        // Add the transaction in the next block.
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        assert_eq!(ledger.confirmations(&transaction_id).unwrap(), Some(1));
+
+        // Append more blocks, and ensure the confirmations increase.
+        for expected in 2..4 {
+            crate::ledger::test_helpers::add_split_block(&mut ledger, &private_key, rng);
+            assert_eq!(ledger.confirmations(&transaction_id).unwrap(), Some(expected));
+        }
+        assert_eq!(ledger.confirmations(&coinbase.id()).unwrap(), Some(4));
+    }
 }
@@ -0,0 +1,232 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+/// The in-memory ledger, used for its stateless helper methods.
+type MemoryLedger<N> = Ledger<N, BlockMemory<N>, ProgramMemory<N>>;
+
+/// A ledger of the header chain, without transactions or a VM.
+#[derive(Clone)]
+pub struct HeaderLedger<N: Network> {
+    /// The current block hash.
+    current_hash: N::BlockHash,
+    /// The current block tree.
+    block_tree: BlockTree<N>,
+    /// The cumulative proof target of the header chain, as a sum of the block weights.
+    cumulative_proof_target: u128,
+    /// The map of block heights to previous block hashes.
+    previous_hashes: IndexMap<u32, N::BlockHash>,
+    /// The map of block heights to block headers.
+    headers: IndexMap<u32, Header<N>>,
+    /// The map of block heights to block signatures.
+    signatures: IndexMap<u32, Signature<N>>,
+    /// The validator set.
+    validators: IndexMap<Address<N>, ()>,
+}
+
+impl<N: Network> HeaderLedger<N> {
+    /// Initializes a new header ledger with the given genesis block, signed by the given validator address.
+    pub fn new_with_genesis(genesis: &Block<N>, address: Address<N>) -> Result<Self> {
+        // Ensure the genesis block is valid.
+        MemoryLedger::<N>::check_genesis_block(genesis)?;
+
+        // Initialize the header ledger.
+        let mut ledger = Self {
+            current_hash: Default::default(),
+            block_tree: MemoryLedger::<N>::new_block_tree()?,
+            cumulative_proof_target: 0,
+            previous_hashes: Default::default(),
+            headers: Default::default(),
+            signatures: Default::default(),
+            validators: [(address, ())].into_iter().collect(),
+        };
+
+        // Add the genesis header.
+        ledger.add_next_header(genesis.previous_hash(), *genesis.header(), *genesis.signature())?;
+        // Ensure the genesis block hash matches.
+        ensure!(ledger.current_hash == genesis.hash(), "The genesis block has an incorrect block hash");
+        Ok(ledger)
+    }
+
+    /// Checks the given header and signature are valid for the next block in the header chain,
+    /// returning the block hash of the next block.
+    pub fn check_next_header(
+        &self,
+        previous_hash: N::BlockHash,
+        header: &Header<N>,
+        signature: &Signature<N>,
+    ) -> Result<N::BlockHash> {
+        // Ensure the previous block hash is correct.
+        ensure!(previous_hash == self.current_hash, "The given header has an incorrect previous block hash");
+
+        // Ensure the block header is valid.
+        ensure!(header.is_valid(), "Invalid block header: {header:?}");
+
+        match self.headers.last() {
+            // Ensure the genesis header is a genesis header.
+            None => ensure!(header.is_genesis(), "The first header is not a genesis header"),
+            Some((_, latest)) => {
+                // Ensure the block height, round, and timestamp are correct for the next block.
+                MemoryLedger::<N>::check_next_header(latest.height(), latest.round(), latest.timestamp(), header)?;
+                // Ensure the previous state root is the current state root.
+                if header.previous_state_root() != self.latest_state_root() {
+                    bail!("The given header has an incorrect previous state root")
+                }
+            }
+        }
+
+        // Ensure the proof target and coinbase target are nonzero.
+        ensure!(header.proof_target() > 0, "The given header has a zero proof target");
+        ensure!(header.coinbase_target() > 0, "The given header has a zero coinbase target");
+
+        // Compute the block hash.
+        let block_hash = MemoryLedger::<N>::compute_block_hash(previous_hash, header)?;
+
+        // Ensure the header is signed by an authorized validator.
+        let signer = signature.to_address();
+        if !self.validators.contains_key(&signer) {
+            bail!("Block {} ({block_hash}) is signed by an unauthorized validator ({signer})", header.height())
+        }
+        // Check the signature.
+        if !signature.verify(&signer, &[*block_hash]) {
+            bail!("Invalid signature for block {} ({block_hash})", header.height())
+        }
+
+        Ok(block_hash)
+    }
+
+    /// Adds the given header and signature as the next block in the header chain.
+    pub fn add_next_header(
+        &mut self,
+        previous_hash: N::BlockHash,
+        header: Header<N>,
+        signature: Signature<N>,
+    ) -> Result<()> {
+        // Ensure the given header is a valid next header, and compute its block hash.
+        let block_hash = self.check_next_header(previous_hash, &header, &signature)?;
+
+        // Update the block tree.
+        let mut block_tree = self.block_tree.clone();
+        block_tree.append(&[block_hash.to_bits_le()])?;
+
+        // Update the header ledger.
+        let height = header.height();
+        self.cumulative_proof_target =
+            self.cumulative_proof_target.saturating_add(MemoryLedger::<N>::compute_block_weight(header.proof_target()));
+        self.previous_hashes.insert(height, previous_hash);
+        self.headers.insert(height, header);
+        self.signatures.insert(height, signature);
+        self.current_hash = block_hash;
+        self.block_tree = block_tree;
+        Ok(())
+    }
+
+    /// Returns the latest block hash.
+    pub const fn latest_hash(&self) -> N::BlockHash {
+        self.current_hash
+    }
+
+    /// Returns the latest block height.
+    pub fn latest_height(&self) -> u32 {
+        self.headers.last().map_or(0, |(height, _)| *height)
+    }
+
+    /// Returns the latest state root.
+    pub const fn latest_state_root(&self) -> &Field<N> {
+        self.block_tree.root()
+    }
+
+    /// Returns the latest block header.
+    pub fn latest_header(&self) -> Result<Header<N>> {
+        self.get_header(self.latest_height())
+    }
+
+    /// Returns the cumulative proof target of the header chain.
+    pub const fn cumulative_proof_target(&self) -> u128 {
+        self.cumulative_proof_target
+    }
+
+    /// Returns the previous block hash for the given block height.
+    pub fn get_previous_hash(&self, height: u32) -> Result<N::BlockHash> {
+        match self.previous_hashes.get(&height) {
+            Some(previous_hash) => Ok(*previous_hash),
+            None => bail!("Missing previous block hash for height {height}"),
+        }
+    }
+
+    /// Returns the block header for the given block height.
+    pub fn get_header(&self, height: u32) -> Result<Header<N>> {
+        match self.headers.get(&height) {
+            Some(header) => Ok(*header),
+            None => bail!("Missing block header for height {height}"),
+        }
+    }
+
+    /// Returns the block signature for the given block height.
+    pub fn get_signature(&self, height: u32) -> Result<Signature<N>> {
+        match self.signatures.get(&height) {
+            Some(signature) => Ok(*signature),
+            None => bail!("Missing block signature for height {height}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_utilities::TestRng;
+
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_header_ledger() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        let address = Address::try_from(&private_key).unwrap();
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add a block to the ledger.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Build a header ledger from the headers of the ledger.
+        let mut header_ledger = HeaderLedger::new_with_genesis(&ledger.get_block(0).unwrap(), address).unwrap();
+        for height in 1..=ledger.latest_height() {
+            let previous_hash = ledger.get_previous_hash(height).unwrap();
+            let header = ledger.get_header(height).unwrap();
+            let signature = ledger.get_signature(height).unwrap();
+            header_ledger.add_next_header(previous_hash, header, signature).unwrap();
+        }
+
+        // Ensure the header ledger matches the ledger.
+        assert_eq!(header_ledger.latest_height(), ledger.latest_height());
+        assert_eq!(header_ledger.latest_hash(), ledger.latest_hash());
+        assert_eq!(header_ledger.latest_state_root(), ledger.latest_state_root());
+        assert_eq!(header_ledger.cumulative_proof_target(), ledger.cumulative_proof_target());
+
+        // Ensure a header cannot be added twice.
+        let header = ledger.get_header(1).unwrap();
+        let signature = ledger.get_signature(1).unwrap();
+        assert!(header_ledger.add_next_header(ledger.get_previous_hash(1).unwrap(), header, signature).is_err());
+    }
+}
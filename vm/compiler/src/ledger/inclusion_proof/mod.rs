@@ -0,0 +1,135 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::ledger::{state_path::check_transaction_paths, BlockPath, HeaderLeaf, HeaderPath, TransactionsPath};
+use console::{network::prelude::*, types::Field};
+
+/// A proof that a transaction is included in a block, and that the block is included in the state root.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionInclusionProof<N: Network> {
+    /// The state root.
+    state_root: N::StateRoot,
+    /// The Merkle path for the block hash.
+    block_path: BlockPath<N>,
+    /// The block hash.
+    block_hash: N::BlockHash,
+    /// The previous block hash.
+    previous_block_hash: N::BlockHash,
+    /// The block header root.
+    header_root: Field<N>,
+    /// The Merkle path for the block header leaf.
+    header_path: HeaderPath<N>,
+    /// The block header leaf.
+    header_leaf: HeaderLeaf<N>,
+    /// The Merkle path for the transaction ID.
+    transactions_path: TransactionsPath<N>,
+    /// The transaction ID.
+    transaction_id: N::TransactionID,
+}
+
+impl<N: Network> TransactionInclusionProof<N> {
+    /// Initializes a new instance of `TransactionInclusionProof`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        state_root: N::StateRoot,
+        block_path: BlockPath<N>,
+        block_hash: N::BlockHash,
+        previous_block_hash: N::BlockHash,
+        header_root: Field<N>,
+        header_path: HeaderPath<N>,
+        header_leaf: HeaderLeaf<N>,
+        transactions_path: TransactionsPath<N>,
+        transaction_id: N::TransactionID,
+    ) -> Result<Self> {
+        // Construct the transaction inclusion proof.
+        let proof = Self {
+            state_root,
+            block_path,
+            block_hash,
+            previous_block_hash,
+            header_root,
+            header_path,
+            header_leaf,
+            transactions_path,
+            transaction_id,
+        };
+        // Ensure the transaction inclusion proof is valid.
+        proof.verify()?;
+        // Return the transaction inclusion proof.
+        Ok(proof)
+    }
+
+    /// Ensures the transaction inclusion proof is valid, by checking the chain
+    /// from the transaction ID to the transactions root, header root, block hash, and state root.
+    pub fn verify(&self) -> Result<()> {
+        check_transaction_paths(
+            &self.state_root,
+            &self.block_path,
+            &self.block_hash,
+            &self.previous_block_hash,
+            &self.header_root,
+            &self.header_path,
+            &self.header_leaf,
+            &self.transactions_path,
+            &self.transaction_id,
+        )
+    }
+
+    /// Returns the state root.
+    pub const fn state_root(&self) -> N::StateRoot {
+        self.state_root
+    }
+
+    /// Returns the block path.
+    pub const fn block_path(&self) -> &BlockPath<N> {
+        &self.block_path
+    }
+
+    /// Returns the block hash.
+    pub const fn block_hash(&self) -> N::BlockHash {
+        self.block_hash
+    }
+
+    /// Returns the previous block hash.
+    pub const fn previous_block_hash(&self) -> N::BlockHash {
+        self.previous_block_hash
+    }
+
+    /// Returns the block header root.
+    pub const fn header_root(&self) -> &Field<N> {
+        &self.header_root
+    }
+
+    /// Returns the header path.
+    pub const fn header_path(&self) -> &HeaderPath<N> {
+        &self.header_path
+    }
+
+    /// Returns the header leaf.
+    pub const fn header_leaf(&self) -> &HeaderLeaf<N> {
+        &self.header_leaf
+    }
+
+    /// Returns the transactions path.
+    pub const fn transactions_path(&self) -> &TransactionsPath<N> {
+        &self.transactions_path
+    }
+
+    /// Returns the transaction ID.
+    pub const fn transaction_id(&self) -> &N::TransactionID {
+        &self.transaction_id
+    }
+}
@@ -16,7 +16,9 @@
 
 use super::*;
 
-impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
+impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>, M: for<'a> Map<'a, N::TransactionID, Transaction<N>>>
+    Ledger<N, B, P, M>
+{
     /* Transaction */
 
     /// Returns an iterator over the transaction IDs, for all transactions in `self`.
@@ -53,6 +55,25 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
         self.transitions.serial_numbers()
     }
 
+    /// Returns an iterator over the serial numbers, with the height of the block that spent them,
+    /// for all transition inputs that are records.
+    pub fn spent_serial_numbers(&self) -> impl '_ + Iterator<Item = (Field<N>, u32)> {
+        self.serial_numbers().flat_map(|serial_number| {
+            // Retrieve the block height of the transition that spent the serial number.
+            let height = self
+                .transitions
+                .find_transition_id(&serial_number)
+                .and_then(|transition_id| self.get_transition_height(&transition_id));
+            match height {
+                Ok((height, _)) => Some((*serial_number, height)),
+                Err(e) => {
+                    warn!("Failed to find the spending height of serial number '{serial_number}': {e}");
+                    None
+                }
+            }
+        })
+    }
+
     /// Returns an iterator over the tags, for all transition inputs that are records.
     pub fn tags(&self) -> impl '_ + Iterator<Item = Cow<'_, Field<N>>> {
         self.transitions.tags()
@@ -87,3 +108,36 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
         self.transitions.tpks()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_utilities::TestRng;
+
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_spent_serial_numbers() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+        assert_eq!(ledger.spent_serial_numbers().count(), 0);
+
+        // Spend a record in each of the next blocks, and collect the serial numbers.
+        let mut expected = Vec::new();
+        for _ in 0..3 {
+            let (_, _, next_block) = crate::ledger::test_helpers::add_split_block(&mut ledger, &private_key, rng);
+            expected.extend(next_block.serial_numbers().map(|serial_number| (*serial_number, next_block.height())));
+        }
+        assert_eq!(expected.len(), 3);
+
+        // Ensure the iterator matches the collected serial numbers.
+        let mut candidate = ledger.spent_serial_numbers().collect::<Vec<_>>();
+        candidate.sort_by_key(|(_, height)| *height);
+        assert_eq!(candidate, expected);
+    }
+}
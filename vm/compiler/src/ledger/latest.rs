@@ -16,7 +16,9 @@
 
 use super::*;
 
-impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
+impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>, M: for<'a> Map<'a, N::TransactionID, Transaction<N>>>
+    Ledger<N, B, P, M>
+{
     /// Returns the latest state root.
     pub const fn latest_state_root(&self) -> &Field<N> {
         self.block_tree.root()
@@ -53,8 +55,8 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
     }
 
     /// Returns the latest block timestamp.
-    pub fn latest_timestamp(&self) -> Result<i64> {
-        Ok(self.get_header(self.current_height)?.timestamp())
+    pub const fn latest_timestamp(&self) -> i64 {
+        self.current_timestamp
     }
 
     /// Returns the latest block transactions.
@@ -251,6 +251,24 @@ mod tests {
         assert!(map.contains_key(&address).unwrap());
     }
 
+    #[test]
+    fn test_range() {
+        // Initialize a map, with keys inserted out of order.
+        let keys = [7u32, 2, 9, 0, 5, 3, 8, 1, 6, 4];
+        let map: MemoryMap<u32, String> = keys.iter().map(|key| (*key, key.to_string())).collect();
+
+        // Ensure the range returns exactly the keys within bounds, in order.
+        let range = map.range(3, 7).map(|(key, value)| (*key, value.into_owned())).collect::<Vec<_>>();
+        assert_eq!(range, (3..7).map(|key| (key, key.to_string())).collect::<Vec<_>>());
+
+        // Ensure the range bounds are clamped to the keys in the map.
+        assert_eq!(map.range(8, 20).map(|(key, _)| *key).collect::<Vec<_>>(), vec![8, 9]);
+        // Ensure an empty range returns no keys.
+        assert_eq!(map.range(5, 5).count(), 0);
+        assert_eq!(map.range(7, 3).count(), 0);
+        assert_eq!(map.range(10, 20).count(), 0);
+    }
+
     #[test]
     fn test_atomic_writes_are_batched() {
         // The number of items that will be inserted into the map.
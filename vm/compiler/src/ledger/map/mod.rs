@@ -66,6 +66,14 @@ pub trait Map<
     /// Finishes an atomic operation, performing all the queued writes.
     ///
     fn finish_atomic(&self) -> Result<()>;
+
+    ///
+    /// Removes all key-value pairs from the map.
+    ///
+    fn clear(&'a self) -> Result<()> {
+        let keys = self.keys().map(|key| *key).collect::<Vec<_>>();
+        keys.iter().try_for_each(|key| self.remove(key))
+    }
 }
 
 /// A trait representing map-like storage operations with read-only capabilities.
@@ -109,6 +117,19 @@ pub trait MapRead<
     /// Returns an iterator over each value in the map.
     ///
     fn values(&'a self) -> Self::Values;
+
+    ///
+    /// Returns an iterator over the key-value pairs with keys in the range `start..end`, in ascending order of key.
+    /// Note: By default, this filters a full iteration of the map. Ordered backends should override this method.
+    ///
+    fn range(&'a self, start: K, end: K) -> impl Iterator<Item = (Cow<'a, K>, Cow<'a, V>)>
+    where
+        K: Ord,
+    {
+        let mut entries = self.iter().filter(|(key, _)| start <= **key && **key < end).collect::<Vec<_>>();
+        entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        entries.into_iter()
+    }
 }
 
 /// This macro executes the given block of operations as a new atomic write batch IFF there is no
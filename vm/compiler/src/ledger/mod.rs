@@ -17,9 +17,30 @@
 mod block;
 pub use block::*;
 
+mod block_metadata;
+pub use block_metadata::*;
+
+mod diff;
+pub use diff::*;
+
+mod error;
+pub use error::*;
+
+mod header_ledger;
+pub use header_ledger::*;
+
+mod inclusion_proof;
+pub use inclusion_proof::*;
+
+mod rate_limit;
+pub use rate_limit::*;
+
 pub mod map;
 pub use map::*;
 
+mod simulation;
+pub use simulation::*;
+
 mod state_path;
 pub use state_path::*;
 
@@ -36,31 +57,48 @@ mod vm;
 pub use vm::*;
 
 mod contains;
+mod dump;
 mod find;
 mod get;
 mod iterators;
 mod latest;
 
-use crate::program::Program;
+use crate::{atomic_write_batch, ledger::map::memory_map::MemoryMap, program::Program};
 use console::{
     account::{Address, GraphKey, PrivateKey, Signature, ViewKey},
     collections::merkle_tree::MerklePath,
     network::{prelude::*, BHPMerkleTree},
-    program::{Ciphertext, Identifier, Plaintext, ProgramID, Record},
+    program::{Balance, Ciphertext, Identifier, Plaintext, ProgramID, Record, Value},
     types::{Field, Group},
 };
 use snarkvm_parameters::testnet3::GenesisBytes;
 
 use anyhow::Result;
-use indexmap::IndexMap;
-use std::borrow::Cow;
+use indexmap::{IndexMap, IndexSet};
+use std::{
+    borrow::Cow,
+    cmp::Reverse,
+    collections::BinaryHeap,
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc,
+    },
+    time::Instant,
+};
 use time::OffsetDateTime;
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
 /// The depth of the Merkle tree for the blocks.
-const BLOCKS_DEPTH: u8 = 32;
+/// Note: This depth is fixed, as the state path circuit is fixed to it, so `Network::BLOCKS_DEPTH` must match it.
+pub(crate) const BLOCKS_DEPTH: u8 = 32;
+
+/// The depth of the Merkle tree for the memory pool.
+const MEMORY_POOL_DEPTH: u8 = 32;
+
+/// The maximum number of transactions in the encrypted memory pool.
+const MAX_ENCRYPTED_MEMORY_POOL_LEN: usize = 1 << 12;
 
 /// The Merkle tree for the block state.
 pub type BlockTree<N> = BHPMerkleTree<N, BLOCKS_DEPTH>;
@@ -81,16 +119,37 @@ pub enum RecordsFilter<N: Network> {
     SlowUnspent(PrivateKey<N>),
 }
 
+/// A handle that decrypts an encrypted memory pool transaction.
+pub type DecryptionHandle<N> = Arc<dyn Fn(&[u8]) -> Result<Transaction<N>> + Send + Sync>;
+
+/// A hook that rejects a block (by returning an error), after the standard block validation.
+pub type BlockVetoHook<N> = Arc<dyn Fn(&Block<N>) -> Result<()> + Send + Sync>;
+
+/// A notification of a record owned by a subscribed view key, with the height of the block containing it.
+pub type RecordNotification<N> = (u32, Record<N, Plaintext<N>>);
+
+/// A change to a mapping, as `(program ID, mapping name, key, value)`, where a value of `None` removes the key.
+type MappingChange<N> = (ProgramID<N>, Identifier<N>, Plaintext<N>, Option<Value<N>>);
+
 #[derive(Clone)]
-pub struct Ledger<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> {
+pub struct Ledger<
+    N: Network,
+    B: BlockStorage<N>,
+    P: ProgramStorage<N>,
+    M: for<'a> Map<'a, N::TransactionID, Transaction<N>> = MemoryMap<<N as Network>::TransactionID, Transaction<N>>,
+> {
     /// The current block hash.
     current_hash: N::BlockHash,
     /// The current block height.
     current_height: u32,
     /// The current round number.
     current_round: u64,
+    /// The current block timestamp.
+    current_timestamp: i64,
     /// The current block tree.
     block_tree: BlockTree<N>,
+    /// The cumulative proof target (i.e. the sum of the block weights) of all blocks in the ledger.
+    cumulative_proof_target: u128,
     /// The block store.
     blocks: BlockStore<N, B>,
     /// The transaction store.
@@ -101,7 +160,33 @@ pub struct Ledger<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> {
     // TODO (howardwu): Update this to retrieve from a validators store.
     validators: IndexMap<Address<N>, ()>,
     /// The memory pool of unconfirmed transactions.
-    memory_pool: IndexMap<N::TransactionID, Transaction<N>>,
+    /// Note: Like the stores, the memory pool storage is shared between clones of the ledger.
+    memory_pool: M,
+    /// The minimum fee (in gates) of a transaction, for it to enter the memory pool.
+    min_fee: u64,
+    /// The minimum fee increase (in gates) required for a transaction to replace a conflicting one in the memory pool.
+    fee_bump_threshold: u64,
+    /// The minimum balance (in gates) of a record output with a public balance, for a transaction to enter the memory pool.
+    dust_threshold: u64,
+    /// The rate limiter of memory pool submissions, if enabled.
+    memory_pool_rate_limiter: Option<RateLimiter<N>>,
+    /// The memory pool of encrypted unconfirmed transactions, keyed by their transaction ID,
+    /// which are only decrypted on admission and for block proposals.
+    encrypted_memory_pool: IndexMap<N::TransactionID, (Vec<u8>, DecryptionHandle<N>)>,
+    /// The subscribers to records owned by their view key, in newly added blocks.
+    record_subscribers: Vec<(ViewKey<N>, Sender<RecordNotification<N>>)>,
+    /// The operator-local metadata of the blocks, which is not part of the canonical blocks.
+    block_metadata: MemoryMap<u32, BlockMetadata>,
+    /// The hooks that may reject a block, after the standard block validation.
+    veto_hooks: Vec<BlockVetoHook<N>>,
+    /// The buffer of orphan blocks, keyed by their previous block hash.
+    orphan_blocks: IndexMap<N::BlockHash, Block<N>>,
+    /// The maximum number of orphan blocks to buffer. If zero, orphan blocks are not buffered.
+    max_orphan_blocks: usize,
+    /// The flag indicating whether adding blocks is paused.
+    is_paused: bool,
+    /// The flag indicating whether the independent checks of the next block run in parallel.
+    parallel_block_checks: bool,
     /// The VM state.
     vm: VM<N, P>,
     // /// The mapping of program IDs to their global state.
@@ -113,14 +198,23 @@ impl<N: Network> Ledger<N, BlockMemory<N>, ProgramMemory<N>> {
     pub fn new(dev: Option<u16>) -> Result<Self> {
         // Load the genesis block.
         let genesis = Block::<N>::from_bytes_le(GenesisBytes::load_bytes())?;
+        // Ensure the genesis block is the expected genesis block for the network.
+        Self::check_genesis_hash(&genesis)?;
         // Initialize the ledger.
         Self::new_with_genesis(&genesis, genesis.signature().to_address(), dev)
     }
 }
 
-impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
+impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>, M: for<'a> Map<'a, N::TransactionID, Transaction<N>>>
+    Ledger<N, B, P, M>
+{
     /// Initializes a new instance of `Ledger` with the given genesis block.
-    pub fn new_with_genesis(genesis: &Block<N>, address: Address<N>, dev: Option<u16>) -> Result<Self> {
+    /// The genesis block hash is not checked against `N::GENESIS_HASH`, so that custom genesis blocks
+    /// may be used (e.g. for development); call `Self::check_genesis_hash` to require the canonical one.
+    pub fn new_with_genesis(genesis: &Block<N>, address: Address<N>, dev: Option<u16>) -> Result<Self>
+    where
+        M: Default,
+    {
         // Initialize the block store.
         let blocks = BlockStore::<N, B>::open(dev)?;
         // Initialize the program store.
@@ -134,29 +228,114 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
         }
 
         // Initialize the ledger.
-        let mut ledger = Self {
+        let mut ledger = Self::new_empty(blocks, vm, address, M::default())?;
+
+        // Add the genesis block.
+        ledger.add_next_block(genesis)?;
+
+        // Return the ledger.
+        Ok(ledger)
+    }
+
+    /// Initializes a new instance of `Ledger` with the given stores, without any blocks,
+    /// and with the given address as the validator.
+    fn new_empty(blocks: BlockStore<N, B>, vm: VM<N, P>, address: Address<N>, memory_pool: M) -> Result<Self> {
+        Ok(Self {
             current_hash: Default::default(),
             current_height: 0,
             current_round: 0,
-            block_tree: N::merkle_tree_bhp(&[])?,
+            current_timestamp: 0,
+            block_tree: Self::new_block_tree()?,
+            cumulative_proof_target: 0,
             transactions: blocks.transaction_store().clone(),
             transitions: blocks.transition_store().clone(),
             blocks,
             // TODO (howardwu): Update this to retrieve from a validators store.
             validators: [(address, ())].into_iter().collect(),
             vm,
-            memory_pool: Default::default(),
-        };
+            memory_pool,
+            min_fee: 0,
+            fee_bump_threshold: 0,
+            dust_threshold: N::DUST_THRESHOLD_IN_GATES,
+            memory_pool_rate_limiter: None,
+            encrypted_memory_pool: Default::default(),
+            record_subscribers: Default::default(),
+            block_metadata: Default::default(),
+            veto_hooks: Default::default(),
+            orphan_blocks: Default::default(),
+            max_orphan_blocks: 0,
+            is_paused: false,
+            parallel_block_checks: true,
+        })
+    }
 
-        // Add the genesis block.
-        ledger.add_next_block(genesis)?;
+    /// Returns a new genesis block, signed by the given private key, with the given supply (in gates)
+    /// minted to the given recipient.
+    /// Note: The genesis block hash is not checked, as the block is for a new network.
+    pub fn generate_genesis<R: Rng + CryptoRng>(
+        vm: &VM<N, P>,
+        private_key: &PrivateKey<N>,
+        recipient: Address<N>,
+        supply: u64,
+        rng: &mut R,
+    ) -> Result<Block<N>> {
+        // Ensure the genesis supply is nonzero.
+        ensure!(supply > 0, "The genesis supply must be nonzero");
+        // Construct the genesis block.
+        let genesis = Block::genesis_with_recipient(vm, private_key, recipient, supply, rng)?;
+        // Ensure the genesis block is valid.
+        Self::check_genesis_block(&genesis)?;
+        Ok(genesis)
+    }
 
-        // Return the ledger.
-        Ok(ledger)
+    /// Ensures the given genesis block has the expected genesis block hash for the network.
+    /// If the network does not declare a genesis block hash, any genesis block is accepted.
+    pub fn check_genesis_hash(genesis: &Block<N>) -> Result<()> {
+        if let Some(expected_hash) = N::GENESIS_HASH {
+            let genesis_hash = genesis.hash();
+            if genesis_hash.to_string() != expected_hash {
+                bail!("The genesis block hash '{genesis_hash}' does not match the expected hash '{expected_hash}'")
+            }
+        }
+        Ok(())
+    }
+
+    /// Ensures the given genesis block does not reference prior state, as there is no state to spend from.
+    pub fn check_genesis_block(genesis: &Block<N>) -> Result<()> {
+        // Ensure the block is a genesis block.
+        ensure!(genesis.is_genesis(), "The given block is not a genesis block");
+        // Ensure the genesis block does not contain serial numbers.
+        if let Some(serial_number) = genesis.serial_numbers().next() {
+            bail!("The genesis block contains an input serial number '{serial_number}'")
+        }
+        // Ensure the genesis block does not contain tags.
+        if let Some(tag) = genesis.tags().next() {
+            bail!("The genesis block contains an input tag '{tag}'")
+        }
+        // Ensure the genesis block does not contain origins.
+        if let Some(origin) = genesis.origins().next() {
+            bail!("The genesis block contains an input origin '{origin}'")
+        }
+        Ok(())
+    }
+
+    /// Initializes a new, empty block tree.
+    /// Note: The block tree depth is fixed, so this fails for a network with a different block tree depth.
+    fn new_block_tree() -> Result<BlockTree<N>> {
+        // Ensure the network block tree depth matches the block tree depth.
+        ensure!(
+            N::BLOCKS_DEPTH == BLOCKS_DEPTH,
+            "The network block tree depth ({}) is not supported, as the block tree depth is fixed to {BLOCKS_DEPTH}",
+            N::BLOCKS_DEPTH
+        );
+        N::merkle_tree_bhp(&[])
     }
 
     /// Initializes the `Ledger` from storage.
-    pub fn open(dev: Option<u16>) -> Result<Self> {
+    pub fn open(dev: Option<u16>) -> Result<Self>
+    where
+        M: Default,
+    {
         // Initialize the block store.
         let blocks = BlockStore::<N, B>::open(dev)?;
         // Initialize the program store.
@@ -166,7 +345,18 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
     }
 
     /// Initializes the `Ledger` from storage.
-    pub fn from(blocks: BlockStore<N, B>, store: ProgramStore<N, P>) -> Result<Self> {
+    pub fn from(blocks: BlockStore<N, B>, store: ProgramStore<N, P>) -> Result<Self>
+    where
+        M: Default,
+    {
+        Self::from_with_memory_pool(blocks, store, M::default())
+    }
+
+    /// Initializes the `Ledger` from storage, with the given memory pool.
+    /// The pending transactions in the memory pool that are no longer valid are removed.
+    pub fn from_with_memory_pool(blocks: BlockStore<N, B>, store: ProgramStore<N, P>, memory_pool: M) -> Result<Self> {
+        // Ensure the stored blocks are consistent.
+        blocks.verify_integrity()?;
         // Initialize a new VM.
         let vm = VM::<N, P>::from(&blocks, store)?;
 
@@ -175,14 +365,28 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
             current_hash: Default::default(),
             current_height: 0,
             current_round: 0,
-            block_tree: N::merkle_tree_bhp(&[])?,
+            current_timestamp: 0,
+            block_tree: Self::new_block_tree()?,
+            cumulative_proof_target: 0,
             transactions: blocks.transaction_store().clone(),
             transitions: blocks.transition_store().clone(),
             blocks,
             // TODO (howardwu): Update this to retrieve from a validators store.
             validators: Default::default(),
             vm,
-            memory_pool: Default::default(),
+            memory_pool,
+            min_fee: 0,
+            fee_bump_threshold: 0,
+            dust_threshold: N::DUST_THRESHOLD_IN_GATES,
+            memory_pool_rate_limiter: None,
+            encrypted_memory_pool: Default::default(),
+            record_subscribers: Default::default(),
+            block_metadata: Default::default(),
+            veto_hooks: Default::default(),
+            orphan_blocks: Default::default(),
+            max_orphan_blocks: 0,
+            is_paused: false,
+            parallel_block_checks: true,
         };
 
         // Fetch the latest height.
@@ -192,6 +396,8 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
             None => {
                 // Load the genesis block.
                 let genesis = Block::<N>::from_bytes_le(GenesisBytes::load_bytes())?;
+                // Ensure the genesis block is the expected genesis block for the network.
+                Self::check_genesis_hash(&genesis)?;
                 // Add the genesis block.
                 ledger.blocks.insert(&genesis)?;
                 // Return the genesis height.
@@ -203,19 +409,8 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
         let genesis_block = ledger.get_block(0)?;
         ledger.add_validator(genesis_block.signature().to_address())?;
 
-        // Fetch the latest block.
-        let block = ledger.get_block(latest_height)?;
-
-        // Set the current hash, height, and round.
-        ledger.current_hash = block.hash();
-        ledger.current_height = block.height();
-        ledger.current_round = block.round();
-
-        // TODO (howardwu): Improve the performance here by using iterators.
-        // Generate the block tree.
-        let hashes: Vec<_> =
-            (0..=latest_height).map(|height| ledger.get_hash(height).map(|hash| hash.to_bits_le())).try_collect()?;
-        ledger.block_tree.append(&hashes)?;
+        // Set the latest block state.
+        ledger.load_latest_block(latest_height)?;
 
         // Safety check the existence of every block.
         (0..=latest_height).into_par_iter().try_for_each(|height| {
@@ -223,48 +418,631 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
             Ok::<_, Error>(())
         })?;
 
+        // Remove the pending transactions that are no longer valid.
+        let pooled_commitments = ledger.pooled_commitments();
+        ledger.retain_memory_pool(|transaction| {
+            ledger.check_transaction_with_pending(transaction, &pooled_commitments).is_ok()
+        })?;
+
         Ok(ledger)
     }
 
+    /// Sets the current hash, height, round, timestamp, block tree, and cumulative proof target,
+    /// from the stored blocks up to the given latest height.
+    fn load_latest_block(&mut self, latest_height: u32) -> Result<()> {
+        // Fetch the latest block.
+        let block = self.get_block(latest_height)?;
+
+        // Set the current hash, height, round, and timestamp.
+        self.current_hash = block.hash();
+        self.current_height = block.height();
+        self.current_round = block.round();
+        self.current_timestamp = block.timestamp();
+
+        // Generate the block tree.
+        self.block_tree = self.compute_block_tree(latest_height)?;
+
+        // Compute the cumulative proof target.
+        self.cumulative_proof_target = 0;
+        for height in 0..=latest_height {
+            let weight = Self::compute_block_weight(self.get_header(height)?.proof_target());
+            self.cumulative_proof_target = self.cumulative_proof_target.saturating_add(weight);
+        }
+        Ok(())
+    }
+
+    /// Returns a new block tree, computed from the stored block hashes up to the given latest height.
+    fn compute_block_tree(&self, latest_height: u32) -> Result<BlockTree<N>> {
+        // TODO (howardwu): Improve the performance here by using iterators.
+        let hashes: Vec<_> =
+            (0..=latest_height).map(|height| self.get_hash(height).map(|hash| hash.to_bits_le())).try_collect()?;
+        let mut block_tree = Self::new_block_tree()?;
+        block_tree.append(&hashes)?;
+        Ok(block_tree)
+    }
+
+    /// Ensures the block tree matches the block tree recomputed from the stored block hashes.
+    pub fn audit_block_tree(&self) -> Result<()> {
+        // Recompute the block tree.
+        let block_tree = self.compute_block_tree(self.latest_height())?;
+        // Ensure the block tree roots match.
+        let (expected_root, root) = (block_tree.root(), self.block_tree.root());
+        if expected_root != root {
+            bail!("The block tree root '{root}' does not match the recomputed block tree root '{expected_root}'")
+        }
+        Ok(())
+    }
+
+    /// Checks the consistency of the full ledger, returning the first inconsistency found.
+    ///
+    /// This audits the block tree, the stored block heights and timestamps, the previous block hash,
+    /// block hash, and signature of each block, and ensures re-finalizing every transaction into a new VM
+    /// reproduces the program state of the ledger.
+    /// Note: This is an expensive check, as every block is retrieved, and every transaction is verified again.
+    pub fn verify_full(&self) -> Result<()> {
+        // Ensure the block tree matches the stored block hashes.
+        self.audit_block_tree()?;
+        // Ensure the stored block heights are contiguous, and the block timestamps are increasing.
+        self.blocks.verify_integrity()?;
+
+        // Initialize a new VM, to re-finalize the transactions.
+        let mut vm = VM::new(ProgramStore::<N, ProgramMemory<N>>::open(self.vm.program_store().dev())?)?;
+
+        let mut previous_hash = N::BlockHash::default();
+        for height in 0..=self.latest_height() {
+            // Retrieve the block, which recomputes the block hash and checks the signature.
+            let block = match self.get_block(height) {
+                Ok(block) => block,
+                Err(error) => bail!("Block {height} is invalid: {error}"),
+            };
+            // Ensure the block hash matches the stored block hash.
+            let stored_hash = self.get_hash(height)?;
+            if block.hash() != stored_hash {
+                bail!("Block {height} has block hash '{}', but the stored block hash is '{stored_hash}'", block.hash())
+            }
+            // Ensure the block links to the previous block.
+            if block.previous_hash() != previous_hash {
+                bail!(
+                    "Block {height} has previous block hash '{}', but the previous block hash is '{previous_hash}'",
+                    block.previous_hash()
+                )
+            }
+            // Ensure the block is well-formed, including its block hash and signature.
+            if let Err(error) = self.check_block_is_well_formed(&block) {
+                bail!("Block {height} is invalid: {error}")
+            }
+            // Re-finalize the block transactions.
+            for transaction in block.transactions().transactions() {
+                if let Err(error) = vm.finalize(transaction) {
+                    bail!("Failed to re-finalize transaction '{}' in block {height}: {error}", transaction.id())
+                }
+            }
+            previous_hash = block.hash();
+        }
+
+        // Ensure the latest block hash matches.
+        if previous_hash != self.current_hash {
+            bail!(
+                "The latest block hash '{}' does not match the stored block hash '{previous_hash}'",
+                self.current_hash
+            )
+        }
+        // Ensure the program state matches the re-finalized program state.
+        // Note: The entries are compared in sorted byte form, as the mapping keys and values are not hashable.
+        let to_sorted_bytes = |key_values: Vec<MappingEntry<N>>| -> Result<Vec<Vec<u8>>> {
+            let mut entries = key_values
+                .into_iter()
+                .map(|(program_id, mapping_name, key, value)| {
+                    Ok([
+                        program_id.to_bytes_le()?,
+                        mapping_name.to_bytes_le()?,
+                        key.to_bytes_le()?,
+                        value.to_bytes_le()?,
+                    ]
+                    .concat())
+                })
+                .collect::<Result<Vec<_>>>()?;
+            entries.sort_unstable();
+            Ok(entries)
+        };
+        let key_values = to_sorted_bytes(self.vm.program_store().key_values()?)?;
+        let expected = to_sorted_bytes(vm.program_store().key_values()?)?;
+        if key_values != expected {
+            bail!("The program state does not match the program state from re-finalizing the transactions")
+        }
+        Ok(())
+    }
+
     /// Returns the VM.
     pub fn vm(&self) -> &VM<N, P> {
         &self.vm
     }
 
     /// Appends the given transaction to the memory pool.
-    pub fn add_to_memory_pool(&mut self, transaction: Transaction<N>) -> Result<()> {
+    ///
+    /// If the transaction spends a serial number of a transaction already in the memory pool,
+    /// it replaces that transaction only if its fee exceeds the existing fee by more than the
+    /// fee bump threshold. On replacement, the replaced transaction is returned.
+    pub fn add_to_memory_pool(&mut self, transaction: Transaction<N>) -> Result<Option<Transaction<N>>> {
+        let transaction_id = transaction.id();
+        // Log the admission decision, with the replaced transaction or the rejection reason.
+        match self.admit_to_memory_pool(transaction) {
+            Ok(replaced) => {
+                let replaced_id = replaced.as_ref().map(|transaction| transaction.id());
+                debug!(%transaction_id, ?replaced_id, "Admitted a transaction to the memory pool");
+                Ok(replaced)
+            }
+            Err(error) => {
+                debug!(%transaction_id, %error, "Rejected a transaction from the memory pool");
+                Err(error)
+            }
+        }
+    }
+
+    /// Appends the given transactions to the memory pool, in order, and returns the result for each transaction.
+    ///
+    /// A transaction that spends a serial number of an earlier admitted transaction in the batch is rejected,
+    /// instead of replacing it, so that the result for the earlier transaction remains accurate.
+    pub fn add_many_to_memory_pool(&mut self, transactions: Vec<Transaction<N>>) -> Vec<Result<()>> {
+        // Map the serial numbers spent by the admitted transactions to their transaction ID.
+        let mut admitted = IndexMap::new();
+
+        let mut results = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            let transaction_id = transaction.id();
+            // Ensure the transaction does not conflict with an earlier admitted transaction in the batch.
+            if let Some(conflict) = transaction.serial_numbers().find_map(|serial_number| admitted.get(serial_number)) {
+                let error = anyhow!(
+                    "Transaction '{transaction_id}' conflicts with transaction '{conflict}' earlier in the batch"
+                );
+                debug!(%transaction_id, %error, "Rejected a transaction from the memory pool");
+                results.push(Err(error));
+                continue;
+            }
+            // Add the transaction to the memory pool.
+            let serial_numbers = transaction.serial_numbers().copied().collect::<Vec<_>>();
+            let result = self.add_to_memory_pool(transaction);
+            if result.is_ok() {
+                admitted.extend(serial_numbers.into_iter().map(|serial_number| (serial_number, transaction_id)));
+            }
+            results.push(result.map(|_| ()));
+        }
+        results
+    }
+
+    /// Returns the commitments produced by the transactions in the memory pool.
+    fn pooled_commitments(&self) -> IndexSet<Field<N>> {
+        self.memory_pool
+            .values()
+            .flat_map(|transaction| transaction.commitments().copied().collect::<Vec<_>>())
+            .collect()
+    }
+
+    /// Adds the given transaction to the memory pool, returning the replaced transaction, if any.
+    fn admit_to_memory_pool(&mut self, transaction: Transaction<N>) -> Result<Option<Transaction<N>>> {
         // Ensure the transaction does not already exist.
-        if self.memory_pool.contains_key(&transaction.id()) {
+        if self.memory_pool.contains_key(&transaction.id())? {
             bail!("Transaction '{}' already exists in the memory pool.", transaction.id());
         }
 
+        // Check that the transaction satisfies the memory pool policy.
+        // Note: The transaction may spend a record produced by a transaction in the memory pool.
+        self.check_memory_pool_policy(&transaction, &self.pooled_commitments())?;
+
+        // Find the transactions in the memory pool that spend the same serial numbers.
+        let conflicts = self
+            .memory_pool
+            .iter()
+            .filter(|(_, pooled)| {
+                pooled.serial_numbers().any(|serial_number| transaction.serial_numbers().any(|s| s == serial_number))
+            })
+            .map(|(transaction_id, _)| *transaction_id)
+            .collect::<Vec<_>>();
+
+        let replaced = match conflicts.as_slice() {
+            [] => None,
+            [conflict] => {
+                // Retrieve the conflicting transaction.
+                let existing = match self.memory_pool.get(conflict)? {
+                    Some(existing) => existing.into_owned(),
+                    None => bail!("Missing transaction '{conflict}' in the memory pool"),
+                };
+                // Ensure the new fee exceeds the existing fee by more than the fee bump threshold.
+                let existing_fee = Self::total_fee(&existing);
+                let fee = Self::total_fee(&transaction);
+                if fee <= existing_fee + self.fee_bump_threshold as i128 {
+                    bail!(
+                        "Transaction '{}' conflicts with transaction '{conflict}' in the memory pool, and its fee ({fee}) does not exceed the existing fee ({existing_fee}) by more than {} gates",
+                        transaction.id(),
+                        self.fee_bump_threshold
+                    );
+                }
+                Some(existing)
+            }
+            _ => bail!("Transaction '{}' conflicts with multiple transactions in the memory pool", transaction.id()),
+        };
+
+        // Ensure the transaction does not exceed the memory pool rate limit, if enabled.
+        // Note: This is checked last, so that invalid transactions do not consume the rate limit.
+        if let Some(limiter) = &mut self.memory_pool_rate_limiter {
+            limiter.acquire(&transaction, Instant::now())?;
+        }
+
+        atomic_write_batch!(self.memory_pool, {
+            // Remove the conflicting transaction from the memory pool.
+            if let Some(existing) = &replaced {
+                self.memory_pool.remove(&existing.id())?;
+            }
+            // Insert the transaction to the memory pool.
+            self.memory_pool.insert(transaction.id(), transaction)
+        });
+        Ok(replaced)
+    }
+
+    /// Returns the given transactions in dependency order, where a transaction that spends a record
+    /// follows the transaction that produced it. Independent transactions are ordered by decreasing fee,
+    /// and then by their original order. Transactions in a dependency cycle are deferred, and omitted.
+    fn order_transactions(transactions: Vec<&Transaction<N>>) -> Vec<&Transaction<N>> {
+        // Map each commitment to the index of the transaction that produced it.
+        let producers = transactions
+            .iter()
+            .enumerate()
+            .flat_map(|(index, transaction)| transaction.commitments().map(move |commitment| (commitment, index)))
+            .collect::<IndexMap<_, _>>();
+
+        // For each transaction, count its dependencies, and record its dependents.
+        let mut num_dependencies = vec![0; transactions.len()];
+        let mut dependents = vec![Vec::new(); transactions.len()];
+        for (index, transaction) in transactions.iter().enumerate() {
+            let dependencies = transaction
+                .origins()
+                .filter_map(|origin| match origin {
+                    Origin::Commitment(commitment) => producers.get(commitment).copied(),
+                    Origin::StateRoot(_) => None,
+                })
+                .collect::<IndexSet<_>>();
+
+            num_dependencies[index] = dependencies.len();
+            for dependency in dependencies {
+                dependents[dependency].push(index);
+            }
+        }
+
+        // Initialize the queue with the transactions that have no dependencies.
+        let mut queue = (0..transactions.len())
+            .filter(|index| num_dependencies[*index] == 0)
+            .map(|index| (Self::total_fee(transactions[index]), Reverse(index)))
+            .collect::<BinaryHeap<_>>();
+
+        // Repeatedly select the highest-fee transaction whose dependencies have been selected.
+        let mut ordered_transactions = Vec::with_capacity(transactions.len());
+        while let Some((_, Reverse(index))) = queue.pop() {
+            ordered_transactions.push(transactions[index]);
+            for dependent in &dependents[index] {
+                num_dependencies[*dependent] -= 1;
+                if num_dependencies[*dependent] == 0 {
+                    queue.push((Self::total_fee(transactions[*dependent]), Reverse(*dependent)));
+                }
+            }
+        }
+        ordered_transactions
+    }
+
+    /// Returns the total fee of the given transaction, summed over all of its transitions.
+    fn total_fee(transaction: &Transaction<N>) -> i128 {
+        transaction.fees().map(|fee| *fee as i128).sum()
+    }
+
+    /// Ensures the serialized size of the given transaction does not exceed the given maximum number of bytes.
+    fn check_transaction_size(transaction: &Transaction<N>, max_bytes: usize) -> Result<()> {
+        let num_bytes = transaction.to_bytes_le()?.len();
+        if num_bytes > max_bytes {
+            bail!(
+                "Transaction '{}' is {num_bytes} bytes, which exceeds the maximum of {max_bytes} bytes",
+                transaction.id()
+            )
+        }
+        Ok(())
+    }
+
+    /// Checks the given transaction satisfies the memory pool policy, where the transaction may also spend the records
+    /// of the given pending commitments. The transaction must not exceed the maximum transaction size, must be
+    /// well formed and unique, must not create dust records, and must pay the minimum fee.
+    /// Note: The memory pool rate limit is not checked, as it is only consumed by admitted transactions.
+    fn check_memory_pool_policy(
+        &self,
+        transaction: &Transaction<N>,
+        pending_commitments: &IndexSet<Field<N>>,
+    ) -> Result<()> {
+        // Check that the transaction does not exceed the maximum transaction size.
+        Self::check_transaction_size(transaction, N::MAX_TRANSACTION_BYTES)?;
         // Check that the transaction is well formed and unique.
-        self.check_transaction(&transaction)?;
+        self.check_transaction_with_pending(transaction, pending_commitments)?;
+        // Check that the transaction does not create dust records.
+        self.check_dust_threshold(transaction)?;
+        // Check that the transaction pays the minimum fee.
+        self.check_min_fee(transaction)
+    }
+
+    /// Ensures the record outputs of the given transaction with a public balance are not below the dust threshold.
+    /// Note: Record outputs with a private balance are exempt, as their balance is not visible.
+    fn check_dust_threshold(&self, transaction: &Transaction<N>) -> Result<()> {
+        for (commitment, record) in transaction.transitions().flat_map(Transition::output_records) {
+            if let Balance::Public(gates) = record.gates() {
+                if **gates < self.dust_threshold {
+                    bail!(
+                        "Record output '{commitment}' has a balance of {} gates, which is below the dust threshold of {} gates",
+                        **gates,
+                        self.dust_threshold
+                    )
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Ensures the total fee of the given transaction is not below the minimum fee.
+    /// Note: If the minimum fee is zero, the check is disabled.
+    fn check_min_fee(&self, transaction: &Transaction<N>) -> Result<()> {
+        let fee = Self::total_fee(transaction);
+        if self.min_fee > 0 && fee < self.min_fee as i128 {
+            bail!(
+                "Transaction '{}' has a fee of {fee} gates, which is below the minimum fee of {} gates",
+                transaction.id(),
+                self.min_fee
+            )
+        }
+        Ok(())
+    }
+
+    /// Adds a hook that may reject a block (by returning an error), after the standard block validation.
+    pub fn add_veto_hook(&mut self, veto_hook: BlockVetoHook<N>) {
+        self.veto_hooks.push(veto_hook);
+    }
+
+    /// Adds the given encrypted transaction to the memory pool.
+    /// The transaction is decrypted (with the given handle) once on admission, to retrieve its transaction ID
+    /// and check it against the memory pool policy, and is then stored opaquely.
+    /// It is only decrypted again, and checked, for block proposals.
+    pub fn add_encrypted_to_memory_pool(
+        &mut self,
+        ciphertext: Vec<u8>,
+        decryption_handle: DecryptionHandle<N>,
+    ) -> Result<()> {
+        // Ensure the encrypted memory pool is not full.
+        if self.encrypted_memory_pool.len() >= MAX_ENCRYPTED_MEMORY_POOL_LEN {
+            bail!("The encrypted memory pool is full ({MAX_ENCRYPTED_MEMORY_POOL_LEN} transactions)");
+        }
+        // Ensure the ciphertext does not exceed the maximum transaction size.
+        if ciphertext.len() > N::MAX_TRANSACTION_BYTES {
+            bail!(
+                "The encrypted transaction is {} bytes, which exceeds the maximum of {} bytes",
+                ciphertext.len(),
+                N::MAX_TRANSACTION_BYTES
+            )
+        }
+
+        // Decrypt the transaction.
+        let transaction = match decryption_handle(&ciphertext) {
+            Ok(transaction) => transaction,
+            Err(error) => bail!("Failed to decrypt an encrypted transaction for the memory pool: {error}"),
+        };
+        let transaction_id = transaction.id();
+        // Ensure the transaction does not already exist.
+        if self.memory_pool.contains_key(&transaction_id)? || self.encrypted_memory_pool.contains_key(&transaction_id) {
+            bail!("Transaction '{transaction_id}' already exists in the memory pool.");
+        }
+        // Check that the transaction satisfies the memory pool policy.
+        self.check_memory_pool_policy(&transaction, &self.pooled_commitments())?;
+        // Ensure the transaction does not exceed the memory pool rate limit, if enabled.
+        if let Some(limiter) = &mut self.memory_pool_rate_limiter {
+            limiter.acquire(&transaction, Instant::now())?;
+        }
+
+        self.encrypted_memory_pool.insert(transaction_id, (ciphertext, decryption_handle));
+        Ok(())
+    }
+
+    /// Returns the valid transactions from the encrypted memory pool, that are not already in the memory pool.
+    fn decrypt_memory_pool(&self) -> Result<Vec<Transaction<N>>> {
+        // Note: A transaction may spend a record produced by a transaction in the memory pool.
+        let pooled_commitments = self.pooled_commitments();
+        let mut transactions = Vec::<Transaction<N>>::new();
+        for (transaction_id, (ciphertext, decryption_handle)) in &self.encrypted_memory_pool {
+            // Decrypt the transaction.
+            let transaction = match decryption_handle(ciphertext) {
+                Ok(transaction) if transaction.id() == *transaction_id => transaction,
+                Ok(transaction) => {
+                    warn!(
+                        "Skipping encrypted transaction '{transaction_id}', as it decrypts to '{}'",
+                        transaction.id()
+                    );
+                    continue;
+                }
+                Err(error) => {
+                    warn!("Skipping encrypted transaction '{transaction_id}' in the memory pool: {error}");
+                    continue;
+                }
+            };
+            // Skip transactions that were added to the memory pool since.
+            if self.memory_pool.contains_key(transaction_id)? {
+                continue;
+            }
+            // Check that the transaction satisfies the memory pool policy.
+            match self.check_memory_pool_policy(&transaction, &pooled_commitments) {
+                Ok(()) => transactions.push(transaction),
+                Err(error) => warn!("Skipping encrypted transaction '{transaction_id}' in the memory pool: {error}"),
+            }
+        }
+        Ok(transactions)
+    }
 
-        // Insert the transaction to the memory pool.
-        self.memory_pool.insert(transaction.id(), transaction);
+    /// Replaces the memory pool with the given transactions.
+    /// If `is_strict` is `true`, the memory pool is only replaced if every transaction is valid.
+    /// Otherwise, the memory pool is replaced with the valid transactions, and invalid transactions are skipped.
+    /// A transaction that spends a serial number of an earlier transaction in the given transactions is invalid.
+    /// Each transaction must satisfy the memory pool policy, and the memory pool rate limit, as on admission.
+    pub fn replace_memory_pool(
+        &mut self,
+        transactions: impl IntoIterator<Item = Transaction<N>>,
+        is_strict: bool,
+    ) -> Result<()> {
+        // Construct the new memory pool.
+        let mut memory_pool = IndexMap::new();
+        // Track the serial numbers spent, and the commitments produced, by the transactions in the new memory pool.
+        let mut serial_numbers = IndexSet::new();
+        let mut commitments = IndexSet::new();
+        // Prepare the rate limiter, which is only updated if the memory pool is replaced.
+        let mut rate_limiter = self.memory_pool_rate_limiter.clone();
+        for transaction in transactions {
+            // Check that the transaction is not a duplicate, does not spend a serial number
+            // of an earlier transaction, satisfies the memory pool policy, and the rate limit.
+            let result = if memory_pool.contains_key(&transaction.id()) {
+                Err(anyhow!("Transaction '{}' already exists in the memory pool.", transaction.id()))
+            } else if let Some(serial_number) = transaction.serial_numbers().find(|sn| serial_numbers.contains(*sn)) {
+                Err(anyhow!(
+                    "Transaction '{}' spends serial number '{serial_number}' of an earlier transaction",
+                    transaction.id()
+                ))
+            } else {
+                // Note: The transaction may spend a record produced by an earlier transaction.
+                self.check_memory_pool_policy(&transaction, &commitments).and_then(|()| match &mut rate_limiter {
+                    Some(limiter) => limiter.acquire(&transaction, Instant::now()),
+                    None => Ok(()),
+                })
+            };
+            match result {
+                // Insert the transaction to the new memory pool.
+                Ok(()) => {
+                    serial_numbers.extend(transaction.serial_numbers().copied());
+                    commitments.extend(transaction.commitments().copied());
+                    memory_pool.insert(transaction.id(), transaction);
+                }
+                // If the replacement is strict, abort without modifying the memory pool.
+                Err(error) if is_strict => bail!("Failed to replace the memory pool: {error}"),
+                // Otherwise, skip the invalid transaction.
+                Err(error) => warn!("Skipping transaction '{}' in the memory pool: {error}", transaction.id()),
+            }
+        }
+
+        // Replace the memory pool.
+        atomic_write_batch!(self.memory_pool, {
+            self.memory_pool.clear()?;
+            for (transaction_id, transaction) in memory_pool {
+                self.memory_pool.insert(transaction_id, transaction)?;
+            }
+            Ok(())
+        });
+        self.memory_pool_rate_limiter = rate_limiter;
+        Ok(())
+    }
+
+    /// Removes all transactions from the memory pool, including the encrypted transactions,
+    /// and returns the number of transactions removed.
+    /// Note: This is fallible, as the memory pool may be backed by storage. On failure, the memory pool is unchanged.
+    pub fn clear_memory_pool(&mut self) -> Result<usize> {
+        let num_cleared = self.memory_pool_len() + self.encrypted_memory_pool.len();
+        atomic_write_batch!(self.memory_pool, { self.memory_pool.clear() });
+        self.encrypted_memory_pool.clear();
+        Ok(num_cleared)
+    }
+
+    /// Removes the transactions from the memory pool that do not satisfy the given predicate.
+    fn retain_memory_pool(&self, predicate: impl Fn(&Transaction<N>) -> bool) -> Result<()> {
+        // Determine the transactions to remove.
+        let transaction_ids = self
+            .memory_pool
+            .values()
+            .filter(|transaction| !predicate(transaction))
+            .map(|transaction| transaction.id())
+            .collect::<Vec<_>>();
+        // Remove the transactions.
+        atomic_write_batch!(self.memory_pool, {
+            transaction_ids.iter().try_for_each(|transaction_id| self.memory_pool.remove(transaction_id))
+        });
         Ok(())
     }
 
+    /// Returns the transaction IDs in the memory pool that are not in the given block,
+    /// and the transaction IDs in the given block that are not in the memory pool.
+    pub fn memory_pool_diff(&self, block: &Block<N>) -> (Vec<N::TransactionID>, Vec<N::TransactionID>) {
+        // Retrieve the transaction IDs in the memory pool.
+        let pooled_ids = self.memory_pool.keys().map(|transaction_id| *transaction_id).collect::<IndexSet<_>>();
+        // Determine the pooled transactions that are not in the block.
+        let not_in_block = pooled_ids
+            .iter()
+            .filter(|transaction_id| !block.transactions().contains_key(*transaction_id))
+            .copied()
+            .collect();
+        // Determine the block transactions that are not in the memory pool.
+        let not_in_pool =
+            block.transaction_ids().filter(|transaction_id| !pooled_ids.contains(*transaction_id)).copied().collect();
+        (not_in_block, not_in_pool)
+    }
+
     /// Returns a candidate for the next block in the ledger.
     pub fn propose_next_block<R: Rng + CryptoRng>(&self, private_key: &PrivateKey<N>, rng: &mut R) -> Result<Block<N>> {
+        // Construct the header and transactions for the block.
+        let (header, transactions) = self.build_next_header()?;
+        // Construct the new block.
+        Block::new(private_key, self.latest_hash(), header, transactions, rng)
+    }
+
+    /// Returns the expected block hash for the given header of the next block in the ledger.
+    pub fn compute_next_block_hash(&self, header: &Header<N>) -> Result<N::BlockHash> {
+        // Compute the block hash as `Hash(previous_hash || header_root)`.
+        let preimage = [self.latest_hash().to_bits_le(), header.to_root()?.to_bits_le()].concat();
+        Ok(N::hash_bhp1024(&preimage)?.into())
+    }
+
+    /// Returns the candidate header and transactions for the next block in the ledger, before the block is signed.
+    pub fn build_next_header(&self) -> Result<(Header<N>, Transactions<N>)> {
         // Construct the transactions for the block.
         let transactions = {
             // TODO (raychu86): Add more sophisticated logic for transaction selection.
 
+            // Decrypt the transactions from the encrypted memory pool.
+            let decrypted_transactions = self.decrypt_memory_pool()?;
+            // Retrieve the transactions from the memory pool.
+            let pooled_transactions =
+                self.memory_pool.values().map(|transaction| transaction.into_owned()).collect::<Vec<_>>();
+
+            // Order the transactions from the memory pool, so that each transaction follows its dependencies.
+            let ordered_transactions =
+                Self::order_transactions(pooled_transactions.iter().chain(&decrypted_transactions).collect());
+
             // Add the transactions from the memory pool that do not have input collisions.
             let mut transcations = Vec::new();
             let mut input_ids = Vec::new();
+            let mut commitments = IndexSet::<&Field<N>>::new();
+            let mut num_transitions = 0;
 
-            'outer: for transaction in self.memory_pool.values() {
+            'outer: for transaction in ordered_transactions {
                 for input_id in transaction.input_ids() {
                     if input_ids.contains(&input_id) {
                         continue 'outer;
                     }
                 }
 
+                // Ensure the transaction only spends records from the ledger, or from an earlier transaction in the block.
+                for origin in transaction.origins() {
+                    if let Origin::Commitment(commitment) = origin {
+                        if !commitments.contains(commitment) && !self.contains_commitment(commitment)? {
+                            continue 'outer;
+                        }
+                    }
+                }
+
+                // Ensure the transaction does not exceed the maximum number of transitions per block.
+                let num_transaction_transitions = transaction.transitions().count();
+                if num_transitions + num_transaction_transitions > N::MAX_TRANSITIONS_PER_BLOCK {
+                    continue 'outer;
+                }
+
                 transcations.push(transaction);
                 input_ids.extend(transaction.input_ids());
+                commitments.extend(transaction.commitments());
+                num_transitions += num_transaction_transitions;
             }
 
             transcations.into_iter().collect::<Transactions<N>>()
@@ -275,7 +1053,7 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
         let state_root = self.latest_state_root();
 
         // TODO (raychu86): Establish the correct round, coinbase target, and proof target.
-        let round = block.round() + 1;
+        let round = self.next_round(0)?;
         let coinbase_target = u64::MAX;
         let proof_target = u64::MAX;
 
@@ -292,14 +1070,105 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
         // Construct the header.
         let header = Header::from(*state_root, transactions.to_root()?, metadata)?;
 
-        // Construct the new block.
-        Block::new(private_key, block.hash(), header, transactions, rng)
+        Ok((header, transactions))
+    }
+
+    /// Checks that the timestamp delta is consistent with the round delta, between the previous block and the next block.
+    /// Note: A block may trail the expected round time by timeouts, so only rounds that advance faster than the clock are rejected.
+    fn check_round_timestamp_skew(
+        previous_round: u64,
+        previous_timestamp: i64,
+        next_round: u64,
+        next_timestamp: i64,
+    ) -> Result<()> {
+        // Compute the minimum elapsed time implied by the round delta.
+        let round_delta = i128::from(next_round.saturating_sub(previous_round));
+        let expected_delta = round_delta * i128::from(N::BLOCK_TIME_SECONDS);
+        // Compute the actual elapsed time.
+        let timestamp_delta = i128::from(next_timestamp) - i128::from(previous_timestamp);
+        // Ensure the timestamp does not trail the round progression by more than the tolerance.
+        if expected_delta - timestamp_delta > i128::from(N::MAX_ROUND_TIMESTAMP_SKEW_IN_SECS) {
+            bail!(
+                "The given block advances {round_delta} rounds in {timestamp_delta} seconds, exceeding the block time of {} seconds",
+                N::BLOCK_TIME_SECONDS
+            )
+        }
+        Ok(())
+    }
+
+    /// Returns the round number of the next block, after the given number of timeouts since the latest round.
+    pub fn next_round(&self, timeouts: u64) -> Result<u64> {
+        Self::next_round_after(self.latest_round(), timeouts)
+    }
+
+    /// Returns the round number of the next block, after the given number of timeouts since the given latest round.
+    fn next_round_after(latest_round: u64, timeouts: u64) -> Result<u64> {
+        match latest_round.checked_add(1).and_then(|round| round.checked_add(timeouts)) {
+            Some(next_round) => Ok(next_round),
+            None => bail!(
+                "The next round number overflows the latest round number ({latest_round}) with {timeouts} timeouts"
+            ),
+        }
+    }
+
+    /// Checks the height, round, and timestamp of the given block header are valid for the next block,
+    /// after the latest block with the given height, round, and timestamp.
+    pub(crate) fn check_next_header(
+        latest_height: u32,
+        latest_round: u64,
+        latest_timestamp: i64,
+        header: &Header<N>,
+    ) -> Result<()> {
+        // Ensure the next block height is correct.
+        match latest_height.checked_add(1) {
+            Some(next_height) if next_height == header.height() => (),
+            Some(_) => bail!("The given block has an incorrect block height"),
+            None => bail!("The next block height overflows the latest block height ({latest_height})"),
+        }
+
+        // TODO (raychu86): Ensure the next round number includes timeouts.
+        let next_round = Self::next_round_after(latest_round, 0 /* block.number_of_timeouts() */)?;
+        // Ensure the next round is correct.
+        if latest_round > 0 && next_round != header.round() {
+            bail!("The given block has an incorrect round number")
+        }
+
+        // TODO (raychu86): Ensure the next block timestamp is the median of proposed blocks.
+        // Ensure the next block timestamp is after the current block timestamp.
+        if header.timestamp() <= latest_timestamp {
+            bail!("The given block timestamp is before the current timestamp")
+        }
+
+        // Ensure the block timestamp is consistent with the number of rounds since the current block.
+        Self::check_round_timestamp_skew(latest_round, latest_timestamp, header.round(), header.timestamp())
+    }
+
+    /// Returns the block hash for the given previous block hash and block header, as `Hash(previous_hash || header_root)`.
+    pub(crate) fn compute_block_hash(previous_hash: N::BlockHash, header: &Header<N>) -> Result<N::BlockHash> {
+        // Compute the Merkle root of the block header.
+        let header_root = match header.to_root() {
+            Ok(root) => root,
+            Err(error) => bail!("Failed to compute the Merkle root of the block header: {error}"),
+        };
+        // Compute the block hash.
+        match N::hash_bhp1024(&[previous_hash.to_bits_le(), header_root.to_bits_le()].concat()) {
+            Ok(block_hash) => Ok(block_hash.into()),
+            Err(error) => bail!("Unable to compute block hash for block {}: {error}", header.height()),
+        }
     }
 
     /// Checks the given block is valid next block.
     pub fn check_next_block(&self, block: &Block<N>) -> Result<()> {
         // Ensure the previous block hash is correct.
         if self.current_hash != block.previous_hash() {
+            // If the block is a well-formed block for a future height, it is an orphan block.
+            if block.height() > self.latest_height().saturating_add(1) && self.check_block_is_well_formed(block).is_ok()
+            {
+                bail!(BlockValidationError::<N>::Orphan {
+                    expected_previous: self.current_hash,
+                    got_previous: block.previous_hash(),
+                })
+            }
             bail!("The given block has an incorrect previous block hash")
         }
 
@@ -308,45 +1177,57 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
             bail!("Block hash '{}' already exists in the ledger", block.hash())
         }
 
-        // Ensure the next block height is correct.
-        if self.latest_height() > 0 && self.latest_height() + 1 != block.height() {
-            bail!("The given block has an incorrect block height")
-        }
-
         // Ensure the block height does not already exist.
         if self.contains_block_height(block.height())? {
             bail!("Block height '{}' already exists in the ledger", block.height())
         }
 
-        // TODO (raychu86): Ensure the next round number includes timeouts.
-        // Ensure the next round is correct.
-        if self.latest_round() > 0 && self.latest_round() + 1 /*+ block.number_of_timeouts()*/ != block.round() {
-            bail!("The given block has an incorrect round number")
-        }
-
-        // TODO (raychu86): Ensure the next block timestamp is the median of proposed blocks.
-        // Ensure the next block timestamp is after the current block timestamp.
-        if block.height() > 0 && block.header().timestamp() <= self.latest_block()?.header().timestamp() {
-            bail!("The given block timestamp is before the current timestamp")
+        match block.height() {
+            // Ensure the genesis block does not reference prior state.
+            0 => Self::check_genesis_block(block)?,
+            // Ensure the block height, round, and timestamp are correct for the next block.
+            _ => Self::check_next_header(
+                self.latest_height(),
+                self.latest_round(),
+                self.latest_timestamp(),
+                block.header(),
+            )?,
         }
 
         // TODO (raychu86): Add proof and coinbase target verification.
 
-        for transaction_id in block.transaction_ids() {
-            // Ensure the transaction in the block do not already exist.
+        // Ensure the transaction in the block do not already exist.
+        self.check_each(&block.transaction_ids().collect::<Vec<_>>(), |transaction_id| {
             if self.contains_transaction_id(transaction_id)? {
                 bail!("Transaction '{transaction_id}' already exists in the ledger")
             }
-        }
+            Ok(())
+        })?;
 
         /* Input */
 
+        // Map each commitment produced in the block to the index of the transaction that produced it.
+        let block_commitments = block
+            .transactions()
+            .values()
+            .enumerate()
+            .flat_map(|(index, transaction)| transaction.commitments().map(move |commitment| (commitment, index)))
+            .collect::<IndexMap<_, _>>();
+        let origins = block
+            .transactions()
+            .values()
+            .enumerate()
+            .flat_map(|(index, transaction)| transaction.origins().map(move |origin| (index, origin)))
+            .collect::<Vec<_>>();
+
         // Ensure that the origin are valid.
-        for origin in block.origins() {
+        self.check_each(&origins, |(index, origin)| {
             match origin {
-                // Check that the commitment exists in the ledger.
+                // Check that the commitment exists in the ledger, or is produced by an earlier transaction in the block.
                 Origin::Commitment(commitment) => {
-                    if !self.contains_commitment(commitment)? {
+                    let is_produced_earlier =
+                        block_commitments.get(commitment).is_some_and(|producer| producer < index);
+                    if !is_produced_earlier && !self.contains_commitment(commitment)? {
                         bail!("The given transaction references a non-existent commitment {}", &commitment)
                     }
                 }
@@ -356,147 +1237,418 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
                     bail!("State roots are currently not supported (yet)")
                 }
             }
-        }
-
-        // Ensure the ledger does not already contain a given serial numbers.
-        for serial_number in block.serial_numbers() {
-            if self.contains_serial_number(serial_number)? {
-                bail!("Serial number '{serial_number}' already exists in the ledger")
-            }
-        }
-
-        /* Output */
+            Ok(())
+        })?;
 
-        // Ensure the ledger does not already contain a given commitments.
-        for commitment in block.commitments() {
-            if self.contains_commitment(commitment)? {
-                bail!("Commitment '{commitment}' already exists in the ledger")
-            }
-        }
+        /* Input & Output */
 
-        // Ensure the ledger does not already contain a given nonces.
-        for nonce in block.nonces() {
-            if self.contains_nonce(nonce)? {
-                bail!("Nonce '{nonce}' already exists in the ledger")
+        // Ensure the ledger does not already contain a given serial numbers, commitments, or nonces.
+        let serial_numbers = block.serial_numbers().map(|serial_number| DuplicateKind::SerialNumber(*serial_number));
+        let commitments = block.commitments().map(|commitment| DuplicateKind::Commitment(*commitment));
+        let nonces = block.nonces().map(|nonce| DuplicateKind::Nonce(*nonce));
+        self.check_each(&serial_numbers.chain(commitments).chain(nonces).collect::<Vec<_>>(), |candidate| {
+            if self.contains_duplicate(candidate)? {
+                bail!("{candidate} already exists in the ledger")
             }
-        }
+            Ok(())
+        })?;
 
         /* Metadata */
 
         // Ensure the ledger does not already contain a given transition public keys.
-        for tpk in block.transition_public_keys() {
+        self.check_each(&block.transition_public_keys().collect::<Vec<_>>(), |tpk| {
             if self.contains_tpk(tpk)? {
                 bail!("Transition public key '{tpk}' already exists in the ledger")
             }
-        }
+            Ok(())
+        })?;
 
-        /* Block Header */
+        /* Block */
 
-        // If the block is the genesis block, check that it is valid.
-        if block.height() == 0 && !block.is_genesis() {
-            bail!("Invalid genesis block");
-        }
+        // Ensure the block is well-formed.
+        self.check_block_is_well_formed(block)?;
 
-        // Ensure the block header is valid.
-        if !block.header().is_valid() {
-            bail!("Invalid block header: {:?}", block.header());
-        }
+        /* Transactions */
 
-        /* Block Hash */
+        // Ensure each transaction is well-formed and unique.
+        // Note: The origins of the transactions are checked above, in the order of the block.
+        let pending_commitments = block_commitments.keys().copied().copied().collect::<IndexSet<_>>();
+        self.check_each(&block.transactions().transactions().collect::<Vec<_>>(), |transaction| {
+            if let Err(error) = self.check_transaction_with_pending(transaction, &pending_commitments) {
+                bail!("Invalid transaction found in the transactions list: {error}")
+            }
+            Ok(())
+        })?;
 
-        // Compute the Merkle root of the block header.
-        let header_root = match block.header().to_root() {
-            Ok(root) => root,
-            Err(error) => bail!("Failed to compute the Merkle root of the block header: {error}"),
-        };
+        /* Fees */
+
+        // Prepare the block height, credits program ID, and genesis function name.
+        let height = block.height();
+        let credits_program_id = ProgramID::from_str("credits.aleo")?;
+        let credits_genesis = Identifier::from_str("genesis")?;
 
-        // Check the block hash.
-        match N::hash_bhp1024(&[block.previous_hash().to_bits_le(), header_root.to_bits_le()].concat()) {
-            Ok(candidate_hash) => {
-                // Ensure the block hash matches the one in the block.
-                if candidate_hash != *block.hash() {
-                    bail!("Block {} ({}) has an incorrect block hash.", block.height(), block.hash());
+        // Ensure the fee is correct for each transition.
+        for transition in block.transitions() {
+            if height > 0 {
+                // Ensure the genesis function is not called.
+                if *transition.program_id() == credits_program_id && *transition.function_name() == credits_genesis {
+                    bail!("The genesis function cannot be called.");
+                }
+                // Ensure the transition fee is not negative.
+                if transition.fee().is_negative() {
+                    bail!("The transition fee cannot be negative.");
                 }
             }
-            Err(error) => {
-                bail!("Unable to compute block hash for block {} ({}): {error}", block.height(), block.hash())
+        }
+
+        /* Veto Hooks */
+
+        // Ensure the block is not rejected by a veto hook.
+        for veto_hook in &self.veto_hooks {
+            if let Err(error) = veto_hook(block) {
+                bail!("Block {} ('{}') was rejected by a veto hook: {error}", block.height(), block.hash())
             }
-        };
+        }
 
-        /* Signature */
+        Ok(())
+    }
 
-        // Ensure the block is signed by an authorized validator.
-        let signer = block.signature().to_address();
-        if !self.validators.contains_key(&signer) {
-            let validator = self.validators.iter().next().unwrap().0;
-            eprintln!("{} {} {} {}", *validator, signer, *validator == signer, self.validators.contains_key(&signer));
-            bail!("Block {} ({}) is signed by an unauthorized validator ({})", block.height(), block.hash(), signer);
+    /// Adds the given block as the next block in the chain.
+    /// If the block already exists in the ledger at its height, this is a no-op.
+    ///
+    /// If orphan buffering is enabled, an orphan block is buffered (and the orphan error is still returned),
+    /// and any buffered orphan blocks that extend the ledger are added after the given block.
+    pub fn add_next_block(&mut self, block: &Block<N>) -> Result<()> {
+        // Skip the validation of a block that already exists in the ledger.
+        if self.contains_block(block)? {
+            debug!("Block {} ('{}') already exists in the ledger", block.height(), block.hash());
+            return Ok(());
+        }
+        if let Err(error) = self.add_next_block_with_path(block) {
+            // Buffer the block, if it is an orphan block.
+            if let Some(BlockValidationError::Orphan { .. }) = error.downcast_ref::<BlockValidationError<N>>() {
+                self.buffer_orphan_block(block.clone());
+            }
+            return Err(error);
         }
+        // Add the buffered orphan blocks that now extend the ledger.
+        self.add_orphan_blocks();
+        Ok(())
+    }
 
-        // Check the signature.
-        if !block.signature().verify(&signer, &[*block.hash()]) {
-            bail!("Invalid signature for block {} ({})", block.height(), block.hash());
+    /// Buffers the given orphan block, evicting the oldest orphan block if the buffer is full.
+    /// If orphan buffering is disabled, this is a no-op.
+    fn buffer_orphan_block(&mut self, block: Block<N>) {
+        if self.max_orphan_blocks == 0 {
+            return;
+        }
+        // Evict the oldest orphan block, if the buffer is full.
+        if !self.orphan_blocks.contains_key(&block.previous_hash())
+            && self.orphan_blocks.len() >= self.max_orphan_blocks
+        {
+            if let Some((_, evicted)) = self.orphan_blocks.shift_remove_index(0) {
+                debug!("Evicted orphan block {} ('{}') from the buffer", evicted.height(), evicted.hash());
+            }
         }
+        debug!("Buffered orphan block {} ('{}')", block.height(), block.hash());
+        self.orphan_blocks.insert(block.previous_hash(), block);
+    }
 
-        /* Transactions */
+    /// Adds the buffered orphan blocks that extend the latest block, in order, until none remain.
+    fn add_orphan_blocks(&mut self) {
+        while let Some(block) = self.orphan_blocks.shift_remove(&self.current_hash) {
+            match self.add_next_block_with_path(&block) {
+                Ok(_) => debug!("Added orphan block {} ('{}')", block.height(), block.hash()),
+                Err(error) => {
+                    warn!("Dropped orphan block {} ('{}'): {error}", block.height(), block.hash());
+                    break;
+                }
+            }
+        }
+    }
 
-        // Compute the transactions root.
-        match block.transactions().to_root() {
-            // Ensure the transactions root matches the one in the block header.
-            Ok(root) => {
-                if &root != block.header().transactions_root() {
-                    bail!(
-                        "Block {} ({}) has an incorrect transactions root: expected {}",
-                        block.height(),
-                        block.hash(),
-                        block.header().transactions_root()
-                    );
+    /// Returns `true` if the given block exists in the ledger at its height.
+    fn contains_block(&self, block: &Block<N>) -> Result<bool> {
+        Ok(self.blocks.get_block_hash(block.height())? == Some(block.hash()))
+    }
+
+    /// Adds the given blocks in order as the next blocks in the chain, stopping at the first block that fails to be added.
+    /// On failure, the ledger remains at the last successfully added block.
+    /// Returns the latest block height of the ledger.
+    pub fn apply_block_stream(&mut self, blocks: impl IntoIterator<Item = Block<N>>) -> Result<u32> {
+        for block in blocks {
+            match self.add_next_block(&block) {
+                Ok(()) => debug!("Applied block {} ('{}') from the block stream", block.height(), block.hash()),
+                Err(error) => {
+                    warn!("Stopped applying the block stream at block {}: {error}", block.height());
+                    break;
                 }
             }
-            Err(error) => bail!("Failed to compute the Merkle root of the block transactions: {error}"),
+        }
+        Ok(self.latest_height())
+    }
+
+    /// Removes the blocks above the given height, and returns their transactions to the memory pool.
+    /// Transactions that are no longer valid, and coinbase transactions, are dropped.
+    /// Note: If the removed blocks contain a `finalize`, the prior mapping values are recovered by re-finalizing
+    /// the remaining blocks into a new VM, which is an expensive operation for a long chain.
+    /// Note: Rolling back more than `N::MAX_REORG_DEPTH` blocks is rejected with `ReorgTooDeep`.
+    pub fn rollback_to_height(&mut self, height: u32) -> Result<()> {
+        ensure!(
+            height < self.latest_height(),
+            "Cannot roll back to block {height}, as the latest block is {}",
+            self.latest_height()
+        );
+        // Ensure the rollback does not exceed the maximum reorg depth.
+        Self::check_reorg_depth(self.latest_height() - height)?;
+
+        // Retrieve the displaced blocks, from the latest block down.
+        let displaced_blocks: Vec<_> =
+            (height + 1..=self.latest_height()).rev().map(|height| self.get_block(height)).try_collect()?;
+
+        // Retrieve the programs deployed in the displaced blocks.
+        let program_ids = displaced_blocks
+            .iter()
+            .flat_map(|block| block.transactions().values())
+            .filter_map(|transaction| match transaction {
+                Transaction::Deploy(_, deployment, _) => Some(*deployment.program_id()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        // If the displaced blocks update program mappings, compute the changes that restore the prior mapping values.
+        let mapping_changes = match displaced_blocks
+            .iter()
+            .any(|block| block.transitions().any(|transition| transition.finalize().is_some()))
+        {
+            true => self.mapping_changes_at_height(height, &program_ids)?,
+            false => Vec::new(),
         };
 
-        // Ensure the transactions list is not empty.
-        if block.transactions().is_empty() {
-            bail!("Cannot validate an empty transactions list");
+        // Remove the displaced blocks, and the programs they deployed, in one atomic batch.
+        let blocks = displaced_blocks.iter().map(|block| (block.height(), block.hash())).collect::<Vec<_>>();
+        self.remove_from_storage(&blocks, &program_ids)?;
+
+        // Restore the prior mapping values.
+        // Note: These changes are not part of the batch, as the mapping updates in a batch do not observe each other.
+        let program_store = self.vm.program_store();
+        for (program_id, mapping_name, key, value) in mapping_changes {
+            match value {
+                Some(value) => program_store.update_key_value(&program_id, &mapping_name, key, value)?,
+                None => program_store.remove_key_value(&program_id, &mapping_name, &key)?,
+            }
         }
 
-        // Ensure the number of transactions is within the allowed range.
-        if block.transactions().len() > Transactions::<N>::MAX_TRANSACTIONS {
-            bail!("Cannot validate a block with more than {} transactions", Transactions::<N>::MAX_TRANSACTIONS);
+        // Now that the batch is committed, reload the latest block state and the VM.
+        self.load_latest_block(height)?;
+        self.vm = VM::from(&self.blocks, self.vm.program_store().clone())?;
+
+        // Return the displaced transactions to the memory pool, in their original order.
+        for transaction in displaced_blocks.iter().rev().flat_map(|block| block.transactions().values()) {
+            if transaction.is_coinbase() {
+                continue;
+            }
+            if let Err(error) = self.add_to_memory_pool(transaction.clone()) {
+                debug!("Dropping transaction '{}' from a displaced block: {error}", transaction.id());
+            }
         }
+        Ok(())
+    }
 
-        // Ensure each transaction is well-formed and unique.
-        if !block.transactions().par_iter().all(|(_, transaction)| self.check_transaction(transaction).is_ok()) {
-            bail!("Invalid transaction found in the transactions list");
+    /// Returns the mapping changes that restore the program mappings to their state at the given block height,
+    /// by re-finalizing the blocks up to the given height into a new VM.
+    /// Note: The mappings of the given removed programs are skipped, as these programs are removed with their mappings.
+    fn mapping_changes_at_height(
+        &self,
+        height: u32,
+        removed_program_ids: &[ProgramID<N>],
+    ) -> Result<Vec<MappingChange<N>>> {
+        // Re-finalize the transactions up to the given height into a new VM.
+        let mut vm = VM::new(ProgramStore::<N, ProgramMemory<N>>::open(self.vm.program_store().dev())?)?;
+        for height in 0..=height {
+            for transaction in self.get_block(height)?.transactions().transactions() {
+                vm.finalize(transaction)?;
+            }
         }
 
-        /* Fees */
+        // Note: The entries are indexed in byte form, as the mapping keys are not hashable.
+        let to_entry_id = |program_id: &ProgramID<N>, mapping_name: &Identifier<N>, key: &Plaintext<N>| {
+            Ok::<_, Error>([program_id.to_bytes_le()?, mapping_name.to_bytes_le()?, key.to_bytes_le()?].concat())
+        };
+        let mut expected = vm
+            .program_store()
+            .key_values()?
+            .into_iter()
+            .map(|entry| Ok((to_entry_id(&entry.0, &entry.1, &entry.2)?, entry)))
+            .collect::<Result<IndexMap<_, _>>>()?;
+
+        let mut changes = Vec::new();
+        for (program_id, mapping_name, key, value) in self.vm.program_store().key_values()? {
+            if removed_program_ids.contains(&program_id) {
+                continue;
+            }
+            match expected.swap_remove(&to_entry_id(&program_id, &mapping_name, &key)?) {
+                // If the value is unchanged, skip the entry.
+                Some((.., expected_value)) if expected_value == value => (),
+                // If the value is changed, restore the prior value.
+                Some((.., expected_value)) => changes.push((program_id, mapping_name, key, Some(expected_value))),
+                // If the key did not exist, remove the key.
+                None => changes.push((program_id, mapping_name, key, None)),
+            }
+        }
+        // Restore the keys that were removed.
+        changes.extend(
+            expected
+                .into_values()
+                .map(|(program_id, mapping_name, key, value)| (program_id, mapping_name, key, Some(value))),
+        );
+        Ok(changes)
+    }
 
-        // Prepare the block height, credits program ID, and genesis function name.
-        let height = block.height();
-        let credits_program_id = ProgramID::from_str("credits.aleo")?;
-        let credits_genesis = Identifier::from_str("genesis")?;
+    /// Removes the given blocks (and their metadata) and the given programs from storage, in one atomic batch.
+    fn remove_from_storage(&self, blocks: &[(u32, N::BlockHash)], program_ids: &[ProgramID<N>]) -> Result<()> {
+        let program_store = self.vm.program_store();
 
-        // Ensure the fee is correct for each transition.
-        for transition in block.transitions() {
-            if height > 0 {
-                // Ensure the genesis function is not called.
-                if *transition.program_id() == credits_program_id && *transition.function_name() == credits_genesis {
-                    bail!("The genesis function cannot be called.");
-                }
-                // Ensure the transition fee is not negative.
-                if transition.fee().is_negative() {
-                    bail!("The transition fee cannot be negative.");
+        // Start an atomic batch write operation across the stores.
+        self.blocks.start_atomic();
+        program_store.start_atomic();
+        self.block_metadata.start_atomic();
+
+        let run_atomic_ops = || -> Result<()> {
+            // Remove the programs.
+            for program_id in program_ids {
+                if program_store.contains_program(program_id)? {
+                    program_store.remove_program(program_id)?;
                 }
             }
+            // Remove the blocks, and their metadata.
+            self.blocks.remove_many(&blocks.iter().map(|(_, block_hash)| *block_hash).collect::<Vec<_>>())?;
+            for (height, _) in blocks {
+                self.block_metadata.remove(height)?;
+            }
+            Ok(())
+        };
+
+        // Abort the batch if any of the operations has failed.
+        if let Err(error) = run_atomic_ops() {
+            self.blocks.abort_atomic();
+            program_store.abort_atomic();
+            self.block_metadata.abort_atomic();
+            return Err(error);
+        }
+
+        // Finish the atomic batch write operation.
+        self.blocks.finish_atomic()?;
+        program_store.finish_atomic()?;
+        self.block_metadata.finish_atomic()
+    }
+
+    /// Ensures the given number of blocks to roll back is within the maximum reorg depth.
+    fn check_reorg_depth(depth: u32) -> Result<()> {
+        if depth > N::MAX_REORG_DEPTH {
+            bail!(ReorgTooDeep { depth, max_depth: N::MAX_REORG_DEPTH })
+        }
+        Ok(())
+    }
+
+    /// Rebuilds the reverse indices of the stored blocks, transactions, and transitions,
+    /// by scanning the blocks in height order.
+    /// Note: This is a recovery tool, for a store with stale or missing index entries.
+    pub fn rebuild_indices(&mut self) -> Result<()> {
+        self.blocks.rebuild_indices()?;
+        // Reload the latest block state.
+        self.load_latest_block(self.latest_height())
+    }
+
+    /// Resets the ledger to the given genesis block, removing all blocks, deployed programs,
+    /// and transactions in the memory pool. The genesis block signer becomes the validator set.
+    /// Note: The ledger configuration, record subscribers, and veto hooks are retained.
+    pub fn reset_to_genesis(&mut self, genesis: &Block<N>) -> Result<()> {
+        // Ensure adding blocks is not paused.
+        if self.is_paused {
+            bail!(LedgerPaused)
         }
+        // Ensure the given block is a genesis block.
+        Self::check_genesis_block(genesis)?;
+        // Ensure the genesis block is a valid next block for a new ledger, with the genesis block signer as the validator.
+        // Note: This is checked before any state is removed, so an invalid genesis block leaves the ledger unchanged.
+        Ledger::<N, BlockMemory<N>, ProgramMemory<N>>::new_empty(
+            BlockStore::open(None)?,
+            VM::new(ProgramStore::open(None)?)?,
+            genesis.signature().to_address(),
+            Default::default(),
+        )?
+        .check_next_block(genesis)?;
+
+        // Retrieve the blocks and their metadata, to restore them if the genesis block fails to be added.
+        let blocks: Vec<_> = (0..=self.latest_height()).map(|height| self.get_block(height)).try_collect()?;
+        let block_metadata: Vec<_> =
+            self.block_metadata.iter().map(|(height, metadata)| (*height, metadata.into_owned())).collect();
+
+        // Remove the blocks, their metadata, and the deployed programs, in one atomic batch.
+        self.remove_all_from_storage()?;
+
+        // Prepare the reset ledger, which replaces this ledger once the genesis block is added.
+        let mut ledger = self.clone();
+        ledger.current_hash = Default::default();
+        ledger.current_height = 0;
+        ledger.current_round = 0;
+        ledger.current_timestamp = 0;
+        ledger.block_tree = Self::new_block_tree()?;
+        ledger.cumulative_proof_target = 0;
+        ledger.validators = [(genesis.signature().to_address(), ())].into_iter().collect();
+        ledger.encrypted_memory_pool.clear();
+        ledger.orphan_blocks.clear();
+        ledger.vm = VM::new(ledger.vm.program_store().clone())?;
 
+        // Add the genesis block.
+        if let Err(error) = ledger.add_next_block(genesis) {
+            // Restore the removed blocks, their metadata, and the deployed programs.
+            self.restore_storage(&blocks, block_metadata)?;
+            return Err(error);
+        }
+
+        // Clear the memory pool, as it may be backed by storage that is shared with this ledger.
+        ledger.memory_pool.clear()?;
+
+        *self = ledger;
         Ok(())
     }
 
-    /// Adds the given block as the next block in the chain.
-    pub fn add_next_block(&mut self, block: &Block<N>) -> Result<()> {
+    /// Removes all of the stored blocks, their metadata, and the deployed programs, in one atomic batch.
+    fn remove_all_from_storage(&self) -> Result<()> {
+        let blocks: Vec<_> =
+            self.blocks.heights().map(|height| self.get_hash(*height).map(|hash| (*height, hash))).try_collect()?;
+        let program_ids = self.program_ids().map(|program_id| program_id.into_owned()).collect::<Vec<_>>();
+        self.remove_from_storage(&blocks, &program_ids)
+    }
+
+    /// Restores the storage to the given blocks and block metadata, by removing the stored state,
+    /// and adding the given blocks and their deployed programs again.
+    fn restore_storage(&mut self, blocks: &[Block<N>], block_metadata: Vec<(u32, BlockMetadata)>) -> Result<()> {
+        // Remove any state that was added.
+        self.remove_all_from_storage()?;
+        // Add the blocks, and finalize them into a new VM.
+        let mut vm = VM::new(self.vm.program_store().clone())?;
+        for block in blocks {
+            self.blocks.insert(block)?;
+            Self::finalize_block(&mut vm, block)?;
+        }
+        self.vm = vm;
+        // Add the block metadata.
+        for (height, metadata) in block_metadata {
+            self.block_metadata.insert(height, metadata)?;
+        }
+        Ok(())
+    }
+
+    /// Adds the given block as the next block in the chain,
+    /// and returns the block path proving the inclusion of the block in the new state root.
+    pub fn add_next_block_with_path(&mut self, block: &Block<N>) -> Result<BlockPath<N>> {
+        // Ensure adding blocks is not paused.
+        if self.is_paused {
+            bail!(LedgerPaused)
+        }
+
         // Ensure the given block is a valid next block.
         self.check_next_block(block)?;
 
@@ -510,39 +1662,144 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
             ledger.current_hash = block.hash();
             ledger.current_height = block.height();
             ledger.current_round = block.round();
+            ledger.current_timestamp = block.timestamp();
             ledger.block_tree.append(&[block.hash().to_bits_le()])?;
+            ledger.cumulative_proof_target =
+                ledger.cumulative_proof_target.saturating_add(Self::compute_block_weight(block.proof_target()));
             ledger.blocks.insert(block)?;
 
-            // Update the VM.
-            for transaction in block.transactions().values() {
-                ledger.vm.finalize(transaction)?;
-            }
-
-            // Clear the memory pool of these transactions.
-            for transaction_id in block.transaction_ids() {
-                ledger.memory_pool.remove(transaction_id);
-            }
+            // Compute the block path for the new block.
+            let block_path = ledger.block_tree.prove(block.height() as usize, &block.hash().to_bits_le())?;
 
-            // Clear the memory pool of the transactions that are now invalid.
-            ledger.memory_pool.retain(|_, transaction| self.check_transaction(transaction).is_ok());
+            // Update the VM.
+            Self::finalize_block(&mut ledger.vm, block)?;
+
+            // Now that the block is finalized, clear the memory pool of these transactions, the transactions
+            // that conflict with the transactions in the block, and the transactions that are now invalid.
+            // Note: The memory pool is only updated here, as it may be backed by storage that is shared with `ledger`.
+            // Note: A transaction may spend a record produced by another transaction that remains in the memory pool.
+            let pooled_commitments = self.pooled_commitments();
+            self.retain_memory_pool(|transaction| {
+                !block.transactions().contains_key(&transaction.id())
+                    && !Self::conflicts_with_block(transaction, block)
+                    && self.check_transaction_with_pending(transaction, &pooled_commitments).is_ok()
+            })?;
+
+            // Clear the encrypted memory pool of the transactions that were added.
+            // Note: The encrypted transactions that are now invalid are skipped when decrypted for block proposals.
+            ledger.encrypted_memory_pool.retain(|transaction_id, _| !block.transactions().contains_key(transaction_id));
 
             *self = Self {
                 current_hash: ledger.current_hash,
                 current_height: ledger.current_height,
                 current_round: ledger.current_round,
+                current_timestamp: ledger.current_timestamp,
                 block_tree: ledger.block_tree,
+                cumulative_proof_target: ledger.cumulative_proof_target,
                 blocks: ledger.blocks,
                 transactions: ledger.transactions,
                 transitions: ledger.transitions,
                 validators: ledger.validators,
                 vm: ledger.vm,
                 memory_pool: ledger.memory_pool,
+                min_fee: ledger.min_fee,
+                fee_bump_threshold: ledger.fee_bump_threshold,
+                dust_threshold: ledger.dust_threshold,
+                memory_pool_rate_limiter: ledger.memory_pool_rate_limiter,
+                encrypted_memory_pool: ledger.encrypted_memory_pool,
+                record_subscribers: ledger.record_subscribers,
+                block_metadata: ledger.block_metadata,
+                veto_hooks: ledger.veto_hooks,
+                orphan_blocks: ledger.orphan_blocks,
+                max_orphan_blocks: ledger.max_orphan_blocks,
+                is_paused: ledger.is_paused,
+                parallel_block_checks: ledger.parallel_block_checks,
             };
+
+            // Notify the subscribers of their records in the new block.
+            self.notify_record_subscribers(block);
+
+            Ok(block_path)
         }
+    }
 
+    /// Finalizes the transactions of the given block in the given VM.
+    /// If a transaction fails to finalize, the error identifies the transaction and its index in the block.
+    fn finalize_block(vm: &mut VM<N, P>, block: &Block<N>) -> Result<()> {
+        for (index, transaction) in block.transactions().values().enumerate() {
+            if let Err(error) = vm.finalize(transaction) {
+                bail!(
+                    "Failed to finalize transaction '{}' (index {index}) in block {}: {error}",
+                    transaction.id(),
+                    block.height()
+                )
+            }
+        }
         Ok(())
     }
 
+    /// Returns `true` if the given transaction shares a serial number, commitment, or nonce with the given block.
+    fn conflicts_with_block(transaction: &Transaction<N>, block: &Block<N>) -> bool {
+        transaction.serial_numbers().any(|serial_number| block.serial_numbers().any(|s| s == serial_number))
+            || transaction.commitments().any(|commitment| block.commitments().any(|c| c == commitment))
+            || transaction.nonces().any(|nonce| block.nonces().any(|n| n == nonce))
+    }
+
+    /// Pauses adding blocks to the ledger, until `resume` is called.
+    /// While paused, adding a block fails with `LedgerPaused`, and reads are unaffected.
+    pub fn pause(&mut self) {
+        self.is_paused = true;
+    }
+
+    /// Resumes adding blocks to the ledger.
+    pub fn resume(&mut self) {
+        self.is_paused = false;
+    }
+
+    /// Returns `true` if adding blocks to the ledger is paused.
+    pub const fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    /// Subscribes to the records owned by the given view key, in the blocks added after this call.
+    /// Each notification contains the height of the block and the decrypted record.
+    pub fn subscribe_records(&mut self, view_key: ViewKey<N>) -> Receiver<RecordNotification<N>> {
+        let (sender, receiver) = channel();
+        self.record_subscribers.push((view_key, sender));
+        receiver
+    }
+
+    /// Sends the records in the given block to the subscribers that own them.
+    /// Subscribers whose receiver has been dropped are removed.
+    fn notify_record_subscribers(&mut self, block: &Block<N>) {
+        if self.record_subscribers.is_empty() {
+            return;
+        }
+
+        // Retrieve the output records of the block.
+        let records =
+            block.transactions().values().flat_map(Transaction::transitions).flat_map(Transition::output_records);
+
+        let mut is_disconnected = vec![false; self.record_subscribers.len()];
+        for (_, record) in records {
+            for ((view_key, sender), is_disconnected) in self.record_subscribers.iter().zip(&mut is_disconnected) {
+                // Ensure the record belongs to the view key.
+                if *is_disconnected || !record.is_owner(view_key) {
+                    continue;
+                }
+                // Decrypt the record, and send it to the subscriber.
+                match record.decrypt(view_key) {
+                    Ok(record) => *is_disconnected = sender.send((block.height(), record)).is_err(),
+                    Err(error) => warn!("Failed to decrypt a record in block {}: {error}", block.height()),
+                }
+            }
+        }
+
+        // Remove the disconnected subscribers.
+        let mut is_disconnected = is_disconnected.into_iter();
+        self.record_subscribers.retain(|_| !is_disconnected.next().unwrap_or(false));
+    }
+
     /// Adds a given address to the validator set.
     pub fn add_validator(&mut self, address: Address<N>) -> Result<()> {
         if self.validators.insert(address, ()).is_some() {
@@ -566,17 +1823,137 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
         &self.block_tree
     }
 
+    /// Returns the cumulative proof target (i.e. the sum of the block weights) of all blocks in the ledger.
+    pub const fn cumulative_proof_target(&self) -> u128 {
+        self.cumulative_proof_target
+    }
+
     /// Returns the validator set.
     pub const fn validators(&self) -> &IndexMap<Address<N>, ()> {
         &self.validators
     }
 
+    /// Returns the minimum fee (in gates) of a transaction, to enter the memory pool.
+    pub const fn min_fee(&self) -> u64 {
+        self.min_fee
+    }
+
+    /// Sets the minimum fee (in gates) of a transaction, to enter the memory pool. If zero, the minimum fee is disabled.
+    pub fn set_min_fee(&mut self, min_fee: u64) {
+        self.min_fee = min_fee;
+    }
+
+    /// Returns the minimum fee increase (in gates) required to replace a conflicting transaction in the memory pool.
+    pub const fn fee_bump_threshold(&self) -> u64 {
+        self.fee_bump_threshold
+    }
+
+    /// Sets the minimum fee increase (in gates) required to replace a conflicting transaction in the memory pool.
+    pub fn set_fee_bump_threshold(&mut self, fee_bump_threshold: u64) {
+        self.fee_bump_threshold = fee_bump_threshold;
+    }
+
+    /// Returns the minimum balance (in gates) of a record output with a public balance, to enter the memory pool.
+    pub const fn dust_threshold(&self) -> u64 {
+        self.dust_threshold
+    }
+
+    /// Sets the minimum balance (in gates) of a record output with a public balance, to enter the memory pool.
+    pub fn set_dust_threshold(&mut self, dust_threshold: u64) {
+        self.dust_threshold = dust_threshold;
+    }
+
+    /// Returns `true` if the independent checks of the next block run in parallel.
+    pub const fn parallel_block_checks(&self) -> bool {
+        self.parallel_block_checks
+    }
+
+    /// Sets whether the independent checks of the next block run in parallel. This is enabled by default.
+    /// Note: Without the `parallel` feature, the checks always run sequentially.
+    pub fn set_parallel_block_checks(&mut self, parallel_block_checks: bool) {
+        self.parallel_block_checks = parallel_block_checks;
+    }
+
+    /// Returns the maximum number of orphan blocks to buffer. If zero, orphan blocks are not buffered.
+    pub const fn max_orphan_blocks(&self) -> usize {
+        self.max_orphan_blocks
+    }
+
+    /// Sets the maximum number of orphan blocks to buffer, evicting the oldest orphan blocks over the limit.
+    /// If zero, orphan blocks are not buffered.
+    pub fn set_max_orphan_blocks(&mut self, max_orphan_blocks: usize) {
+        self.max_orphan_blocks = max_orphan_blocks;
+        let num_evicted = self.orphan_blocks.len().saturating_sub(max_orphan_blocks);
+        self.orphan_blocks.drain(..num_evicted);
+    }
+
+    /// Returns the number of buffered orphan blocks.
+    pub fn num_orphan_blocks(&self) -> usize {
+        self.orphan_blocks.len()
+    }
+
+    /// Returns the rate limit of memory pool submissions, if enabled.
+    pub fn memory_pool_rate_limit(&self) -> Option<RateLimit> {
+        self.memory_pool_rate_limiter.as_ref().map(|limiter| limiter.limit())
+    }
+
+    /// Sets the rate limit of memory pool submissions, or disables it if `None` is given.
+    /// Note: Submissions exceeding the rate limit are rejected with `MemoryPoolRateLimited`.
+    pub fn set_memory_pool_rate_limit(&mut self, limit: Option<RateLimit>) {
+        self.memory_pool_rate_limiter = limit.map(RateLimiter::new);
+    }
+
+    /// Sets the operator-local metadata of the block at the given height.
+    /// Note: The metadata is not part of the canonical block, and does not affect the block hash or block tree.
+    pub fn set_block_metadata(&mut self, height: u32, metadata: BlockMetadata) -> Result<()> {
+        ensure!(self.contains_block_height(height)?, "Block {height} does not exist in the ledger");
+        self.block_metadata.insert(height, metadata)
+    }
+
+    /// Returns the operator-local metadata of the block at the given height, if it was set.
+    pub fn get_block_metadata(&self, height: u32) -> Result<Option<BlockMetadata>> {
+        Ok(self.block_metadata.get(&height)?.map(|metadata| metadata.into_owned()))
+    }
+
+    /// Returns the root of a Merkle tree over the sorted transaction IDs in the memory pool,
+    /// which matches for two memory pools if and only if they contain the same transactions.
+    /// Note: The encrypted memory pool is not included.
+    pub fn memory_pool_root(&self) -> Result<Field<N>> {
+        // Sort the transaction IDs.
+        let mut transaction_ids = self.memory_pool.keys().map(|transaction_id| **transaction_id).collect::<Vec<_>>();
+        transaction_ids.sort_unstable();
+        // Prepare the leaves.
+        let leaves = transaction_ids.into_iter().map(|transaction_id| transaction_id.to_bits_le()).collect::<Vec<_>>();
+        // Compute the memory pool root.
+        Ok(*N::merkle_tree_bhp::<MEMORY_POOL_DEPTH>(&leaves)?.root())
+    }
+
+    /// Returns the number of transactions in the memory pool.
+    pub fn memory_pool_len(&self) -> usize {
+        self.memory_pool.keys().count()
+    }
+
+    /// Returns the number of transactions in the encrypted memory pool.
+    pub fn encrypted_memory_pool_len(&self) -> usize {
+        self.encrypted_memory_pool.len()
+    }
+
     /// Returns the memory pool.
-    pub const fn memory_pool(&self) -> &IndexMap<N::TransactionID, Transaction<N>> {
+    pub const fn memory_pool(&self) -> &M {
         &self.memory_pool
     }
 
-    /// Returns a state path for the given commitment.
+    /// Returns a state path for the given commitment, or `None` if the commitment does not exist.
+    /// Note: An error indicates the commitment exists, but its transition, transaction, or block is missing or inconsistent.
+    pub fn to_state_path_checked(&self, commitment: &Field<N>) -> Result<Option<StatePath<N>>> {
+        match self.contains_commitment(commitment)? {
+            true => self.to_state_path(commitment).map(Some),
+            false => Ok(None),
+        }
+    }
+
+    /// Returns a state path for the given commitment, which proves its inclusion in the latest state root.
+    /// Note: This method fails if the commitment does not exist. To distinguish a missing commitment, use `to_state_path_checked`.
     pub fn to_state_path(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
         // Ensure the commitment exists.
         if !self.contains_commitment(commitment)? {
@@ -622,7 +1999,12 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
 
         // Construct the transactions path.
         let transactions = block.transactions();
-        let transaction_index = transactions.iter().position(|(id, _)| id == &transaction.id()).unwrap();
+        let transaction_index = match transactions.iter().position(|(id, _)| id == &transaction.id()) {
+            Some(transaction_index) => transaction_index,
+            None => {
+                bail!("The transaction '{transaction_id}' for commitment '{commitment}' is not in block '{block_hash}'")
+            }
+        };
         let transactions_path = transactions.to_path(transaction_index, *transaction.id())?;
 
         // Construct the block header path.
@@ -652,27 +2034,218 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
         )
     }
 
-    /// Returns the expected coinbase target given the previous block and expected next block details.
-    pub fn compute_coinbase_target(_anchor_block_header: &Header<N>, _block_timestamp: i64, _block_height: u32) -> u64 {
-        unimplemented!()
-    }
+    /// Returns a transaction inclusion proof for the given transaction ID.
+    pub fn to_transaction_inclusion_proof(
+        &self,
+        transaction_id: &N::TransactionID,
+    ) -> Result<TransactionInclusionProof<N>> {
+        // Find the block that contains the transaction.
+        let block_hash = match self.blocks.find_block_hash(transaction_id)? {
+            Some(block_hash) => block_hash,
+            None => bail!("The block hash for transaction '{transaction_id}' is not in the ledger"),
+        };
+        // Retrieve the block.
+        let block = match self.blocks.get_block(&block_hash)? {
+            Some(block) => block,
+            None => bail!("The block '{block_hash}' for transaction '{transaction_id}' is not in the ledger"),
+        };
 
-    /// Returns the expected proof target given the previous block and expected next block details.
-    pub fn compute_proof_target(_anchor_block_header: &Header<N>, _block_timestamp: i64, _block_height: u32) -> u64 {
-        unimplemented!()
-    }
+        // Construct the transactions path.
+        let transactions = block.transactions();
+        let transaction_index = match transactions.get_index_of(transaction_id) {
+            Some(transaction_index) => transaction_index,
+            None => bail!("The transaction '{transaction_id}' is not in block '{block_hash}'"),
+        };
+        let transactions_path = transactions.to_path(transaction_index, **transaction_id)?;
 
-    /// Checks the given transaction is well formed and unique.
-    pub fn check_transaction(&self, transaction: &Transaction<N>) -> Result<()> {
-        let transaction_id = transaction.id();
+        // Construct the block header path.
+        let block_header = block.header();
+        let header_root = block_header.to_root()?;
+        let header_leaf = HeaderLeaf::<N>::new(1, *block_header.transactions_root());
+        let header_path = block_header.to_path(&header_leaf)?;
 
-        // Ensure the transaction is valid.
-        if !self.vm.verify(transaction) {
-            bail!("Transaction '{transaction_id}' is invalid")
-        }
+        // Construct the state root and block path.
+        let state_root = *self.block_tree.root();
+        let block_path = self.block_tree.prove(block.height() as usize, &block.hash().to_bits_le())?;
 
-        // Ensure the ledger does not already contain the given transaction ID.
-        if self.contains_transaction_id(&transaction_id)? {
+        TransactionInclusionProof::new(
+            state_root.into(),
+            block_path,
+            block.hash(),
+            block.previous_hash(),
+            header_root,
+            header_path,
+            header_leaf,
+            transactions_path,
+            *transaction_id,
+        )
+    }
+
+    /// Returns the block path proving that the block committed to by the given historical state root,
+    /// is in the current block tree.
+    pub fn state_root_inclusion_proof(&self, state_root: &Field<N>) -> Result<BlockPath<N>> {
+        // Find the height of the latest block committed to by the state root.
+        let height = match self.find_state_root_height(state_root)? {
+            Some(height) => height,
+            None => bail!("State root '{state_root}' does not exist in the ledger"),
+        };
+        // Construct the block path for the block hash.
+        let block_hash = self.get_hash(height)?;
+        self.block_tree.prove(height as usize, &block_hash.to_bits_le())
+    }
+
+    /// Returns the height of the latest block committed to by the given state root, if it exists.
+    fn find_state_root_height(&self, state_root: &Field<N>) -> Result<Option<u32>> {
+        // Check the latest state root.
+        if state_root == self.latest_state_root() {
+            return Ok(Some(self.latest_height()));
+        }
+        // Check the previous state roots, as the state root after block `h` is the previous state root of block `h + 1`.
+        for height in 1..=self.latest_height() {
+            if self.get_header(height)?.previous_state_root() == state_root {
+                return Ok(Some(height - 1));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the height of the anchor block for the block at the given height.
+    /// The anchor block is the last retarget boundary preceding the given height.
+    pub const fn anchor_block_height(height: u32) -> u32 {
+        (height.saturating_sub(1) / N::ANCHOR_BLOCK_INTERVAL) * N::ANCHOR_BLOCK_INTERVAL
+    }
+
+    /// Returns the header of the anchor block for the block at the given height,
+    /// for use in `compute_coinbase_target` and `compute_proof_target`.
+    pub fn anchor_block_header(&self, height: u32) -> Result<Header<N>> {
+        self.get_header(Self::anchor_block_height(height))
+    }
+
+    /// Returns the weight of a block with the given proof target, where a lower proof target yields a greater weight.
+    pub const fn compute_block_weight(proof_target: u64) -> u128 {
+        match proof_target {
+            0 => u64::MAX as u128,
+            _ => (u64::MAX / proof_target) as u128,
+        }
+    }
+
+    /// Returns `true` if the given fork has a greater cumulative proof target than the canonical chain.
+    /// The fork is given as a contiguous sequence of blocks, with the first block building on a block in the ledger.
+    /// Note: This method only compares the weights of the chains, and does not check the validity of the fork.
+    pub fn is_preferred_fork(&self, fork: &[Block<N>]) -> Result<bool> {
+        // Retrieve the first block of the fork.
+        let first_block = match fork.first() {
+            Some(block) => block,
+            None => return Ok(false),
+        };
+        // Ensure the fork builds on a block in the ledger.
+        let fork_height = first_block.height();
+        ensure!(
+            fork_height > 0 && fork_height <= self.latest_height() + 1,
+            "The fork at height {fork_height} is invalid"
+        );
+        ensure!(
+            self.get_hash(fork_height - 1)? == first_block.previous_hash(),
+            "The fork at height {fork_height} does not build on the ledger"
+        );
+        // Ensure the fork is contiguous.
+        for window in fork.windows(2) {
+            let (previous, block) = (&window[0], &window[1]);
+            ensure!(
+                block.height() == previous.height() + 1 && block.previous_hash() == previous.hash(),
+                "The fork is not contiguous at height {}",
+                block.height()
+            );
+        }
+
+        // Compute the weight of the canonical blocks replaced by the fork.
+        let mut canonical_weight = 0u128;
+        for height in fork_height..=self.latest_height() {
+            canonical_weight =
+                canonical_weight.saturating_add(Self::compute_block_weight(self.get_header(height)?.proof_target()));
+        }
+        // Compute the weight of the fork.
+        let fork_weight = fork
+            .iter()
+            .fold(0u128, |weight, block| weight.saturating_add(Self::compute_block_weight(block.proof_target())));
+
+        Ok(fork_weight > canonical_weight)
+    }
+
+    /// Returns the expected coinbase target given the previous block and expected next block details.
+    pub fn compute_coinbase_target(anchor_block_header: &Header<N>, block_timestamp: i64, block_height: u32) -> u64 {
+        Self::retarget(
+            anchor_block_header.coinbase_target(),
+            anchor_block_header.timestamp(),
+            anchor_block_header.height(),
+            block_timestamp,
+            block_height,
+            N::BLOCK_TIME_SECONDS,
+        )
+    }
+
+    /// Returns the expected proof target given the previous block and expected next block details.
+    pub fn compute_proof_target(anchor_block_header: &Header<N>, block_timestamp: i64, block_height: u32) -> u64 {
+        Self::retarget(
+            anchor_block_header.proof_target(),
+            anchor_block_header.timestamp(),
+            anchor_block_header.height(),
+            block_timestamp,
+            block_height,
+            N::BLOCK_TIME_SECONDS,
+        )
+    }
+
+    /// Returns the anchor target, scaled by the ratio of the elapsed time to the expected time since the anchor block.
+    /// A lower target is harder, so blocks produced faster than the block time lower the target.
+    /// Note: The ratio is bounded to `[1/4, 4]`, and the target is bounded to `[1, u64::MAX]`.
+    fn retarget(
+        anchor_target: u64,
+        anchor_timestamp: i64,
+        anchor_height: u32,
+        block_timestamp: i64,
+        block_height: u32,
+        block_time_seconds: i64,
+    ) -> u64 {
+        // Compute the expected time since the anchor block.
+        let num_blocks = i128::from(block_height.saturating_sub(anchor_height).max(1));
+        let expected_time = (num_blocks * i128::from(block_time_seconds)).max(1);
+        // Compute the elapsed time since the anchor block.
+        let elapsed_time = (i128::from(block_timestamp) - i128::from(anchor_timestamp))
+            .clamp((expected_time / 4).max(1), expected_time.saturating_mul(4));
+        // Scale the anchor target.
+        let target = i128::from(anchor_target).saturating_mul(elapsed_time) / expected_time;
+        target.clamp(1, i128::from(u64::MAX)) as u64
+    }
+
+    /// Verifies the proofs and signatures of the given transaction, without checking it against the ledger.
+    /// Note: This does not ensure the transaction is unique, use `check_transaction` for that.
+    pub fn verify_transaction(&self, transaction: &Transaction<N>) -> Result<()> {
+        match self.vm.verify(transaction) {
+            true => Ok(()),
+            false => bail!("Transaction '{}' is invalid", transaction.id()),
+        }
+    }
+
+    /// Checks the given transaction is well formed and unique.
+    pub fn check_transaction(&self, transaction: &Transaction<N>) -> Result<()> {
+        self.check_transaction_with_pending(transaction, &IndexSet::new())
+    }
+
+    /// Checks the given transaction is well formed and unique, where the transaction may also spend the records
+    /// of the given pending commitments, which are produced by transactions that are not yet in the ledger.
+    fn check_transaction_with_pending(
+        &self,
+        transaction: &Transaction<N>,
+        pending_commitments: &IndexSet<Field<N>>,
+    ) -> Result<()> {
+        let transaction_id = transaction.id();
+
+        // Ensure the transaction is valid.
+        self.verify_transaction(transaction)?;
+
+        // Ensure the ledger does not already contain the given transaction ID.
+        if self.contains_transaction_id(&transaction_id)? {
             bail!("Transaction '{transaction_id}' already exists in the ledger")
         }
 
@@ -702,9 +2275,9 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
         // Ensure that the origin are valid.
         for origin in transaction.origins() {
             match origin {
-                // Check that the commitment exists in the ledger.
+                // Check that the commitment exists in the ledger, or is pending.
                 Origin::Commitment(commitment) => {
-                    if !self.contains_commitment(commitment)? {
+                    if !pending_commitments.contains(commitment) && !self.contains_commitment(commitment)? {
                         bail!("The given transaction references a non-existent commitment {}", &commitment)
                     }
                 }
@@ -768,6 +2341,92 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
         Ok(())
     }
 
+    /// Checks each of the given items, returning the error of the first failing item, in order.
+    /// If parallel block checks are enabled, the items are checked in parallel, and the same error is returned.
+    fn check_each<T: Sync>(&self, items: &[T], check: impl Fn(&T) -> Result<()> + Send + Sync) -> Result<()> {
+        #[cfg(feature = "parallel")]
+        if self.parallel_block_checks {
+            return items.par_iter().map(check).find_first(|result| result.is_err()).unwrap_or(Ok(()));
+        }
+        items.iter().try_for_each(check)
+    }
+
+    /// Checks the given block is well-formed, independent of the blocks, transactions, and transitions in the ledger.
+    fn check_block_is_well_formed(&self, block: &Block<N>) -> Result<()> {
+        /* Block Header */
+
+        // If the block is the genesis block, check that it is valid.
+        if block.height() == 0 && !block.is_genesis() {
+            bail!("Invalid genesis block");
+        }
+
+        // Ensure the block header is valid.
+        if !block.header().is_valid() {
+            bail!("Invalid block header: {:?}", block.header());
+        }
+
+        /* Block Hash */
+
+        // Ensure the block hash matches the one in the block.
+        if Self::compute_block_hash(block.previous_hash(), block.header())? != block.hash() {
+            bail!("Block {} ({}) has an incorrect block hash.", block.height(), block.hash());
+        }
+
+        /* Signature */
+
+        // Ensure the block is signed by an authorized validator.
+        let signer = block.signature().to_address();
+        if !self.validators.contains_key(&signer) {
+            let validator = self.validators.iter().next().unwrap().0;
+            eprintln!("{} {} {} {}", *validator, signer, *validator == signer, self.validators.contains_key(&signer));
+            bail!("Block {} ({}) is signed by an unauthorized validator ({})", block.height(), block.hash(), signer);
+        }
+
+        // Check the signature.
+        if !block.signature().verify(&signer, &[*block.hash()]) {
+            bail!("Invalid signature for block {} ({})", block.height(), block.hash());
+        }
+
+        /* Transactions */
+
+        // Compute the transactions root.
+        match block.transactions().to_root() {
+            // Ensure the transactions root matches the one in the block header.
+            Ok(root) => {
+                if &root != block.header().transactions_root() {
+                    bail!(
+                        "Block {} ({}) has an incorrect transactions root: expected {}",
+                        block.height(),
+                        block.hash(),
+                        block.header().transactions_root()
+                    );
+                }
+            }
+            Err(error) => bail!("Failed to compute the Merkle root of the block transactions: {error}"),
+        };
+
+        // Ensure the number of transactions is within the allowed range.
+        if block.transactions().len() > Transactions::<N>::MAX_TRANSACTIONS {
+            bail!("Cannot validate a block with more than {} transactions", Transactions::<N>::MAX_TRANSACTIONS);
+        }
+
+        // Ensure the number of transitions is within the allowed range.
+        Self::check_num_transitions(block.transactions(), N::MAX_TRANSITIONS_PER_BLOCK)?;
+
+        Ok(())
+    }
+
+    /// Ensures the number of transitions in the given transactions does not exceed the given maximum.
+    fn check_num_transitions(transactions: &Transactions<N>, max_transitions: usize) -> Result<()> {
+        // Count the number of transitions across all transactions.
+        let num_transitions = transactions.transitions().count();
+        // Ensure the number of transitions is within the allowed range.
+        if num_transitions > max_transitions {
+            bail!("Cannot validate a block with {num_transitions} transitions (maximum is {max_transitions})")
+        }
+        Ok(())
+    }
+
     // /// Adds the given transaction to the transaction store.
     // pub fn insert(&mut self, transaction: Transaction<N>) -> Result<()> {
     //     // Check that there are not collisions with existing transactions.
@@ -843,7 +2502,7 @@ impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>> Ledger<N, B, P> {
 pub(crate) mod test_helpers {
     use super::*;
     use crate::ledger::Block;
-    use console::{account::PrivateKey, network::Testnet3};
+    use console::{account::PrivateKey, network::Testnet3, program::Value};
     use snarkvm_utilities::TestRng;
 
     use once_cell::sync::OnceCell;
@@ -890,7 +2549,7 @@ pub(crate) mod test_helpers {
 
     pub(crate) fn sample_genesis_ledger(rng: &mut TestRng) -> CurrentLedger {
         static INSTANCE: OnceCell<CurrentLedger> = OnceCell::new();
-        INSTANCE
+        let mut ledger = INSTANCE
             .get_or_init(|| {
                 // Sample the genesis private key.
                 let private_key = sample_genesis_private_key(rng);
@@ -907,7 +2566,190 @@ pub(crate) mod test_helpers {
 
                 ledger
             })
-            .clone()
+            .clone();
+        // Ensure the memory pool is not shared with the other sampled ledgers.
+        ledger.memory_pool = Default::default();
+        ledger
+    }
+
+    /// Returns a new genesis ledger, whose storage is not shared with the ledgers from `sample_genesis_ledger`.
+    pub(crate) fn sample_new_genesis_ledger(rng: &mut TestRng) -> CurrentLedger {
+        // Sample the genesis private key.
+        let private_key = sample_genesis_private_key(rng);
+        // Sample the genesis block.
+        let genesis = sample_genesis_block_with_pk(rng, private_key);
+        // Initialize the ledger with the genesis block and the associated private key.
+        CurrentLedger::new_with_genesis(&genesis, Address::try_from(&private_key).unwrap(), None).unwrap()
+    }
+
+    /// Returns an unspent record with a non-zero balance, and its commitment, that belongs to the given view key.
+    pub(crate) fn sample_unspent_record(
+        ledger: &CurrentLedger,
+        view_key: &ViewKey<CurrentNetwork>,
+    ) -> (Field<CurrentNetwork>, Record<CurrentNetwork, Plaintext<CurrentNetwork>>) {
+        ledger
+            .find_records(view_key, RecordsFilter::Unspent)
+            .unwrap()
+            .find(|(_, record)| !record.gates().is_zero())
+            .unwrap()
+    }
+
+    /// Returns a transaction that splits the given record in half, using `credits.aleo/split`.
+    pub(crate) fn sample_split_transaction(
+        ledger: &CurrentLedger,
+        private_key: &PrivateKey<CurrentNetwork>,
+        record: Record<CurrentNetwork, Plaintext<CurrentNetwork>>,
+        rng: &mut TestRng,
+    ) -> Transaction<CurrentNetwork> {
+        let amount = Value::from_str(&format!("{}u64", ***record.gates() / 2)).unwrap();
+        Transaction::execute(
+            ledger.vm(),
+            private_key,
+            &ProgramID::from_str("credits.aleo").unwrap(),
+            Identifier::from_str("split").unwrap(),
+            &[Value::Record(record), amount],
+            None,
+            rng,
+        )
+        .unwrap()
+    }
+
+    /// Splits an unspent record of the given private key in the next block, and adds the block to the ledger.
+    /// Returns the commitment of the spent record, the split transaction, and the block.
+    pub(crate) fn add_split_block(
+        ledger: &mut CurrentLedger,
+        private_key: &PrivateKey<CurrentNetwork>,
+        rng: &mut TestRng,
+    ) -> (Field<CurrentNetwork>, Transaction<CurrentNetwork>, Block<CurrentNetwork>) {
+        // Retrieve an unspent record with a non-zero balance.
+        let (commitment, record) = sample_unspent_record(ledger, &ViewKey::try_from(private_key).unwrap());
+        // Split the record in the next block.
+        let transaction = sample_split_transaction(ledger, private_key, record, rng);
+        ledger.add_to_memory_pool(transaction.clone()).unwrap();
+        let next_block = ledger.propose_next_block(private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        (commitment, transaction, next_block)
+    }
+
+    /// Deploys a program with the given name, whose `mint` function outputs a publicly-owned record with no gates,
+    /// and returns the program.
+    /// Note: Ledgers from `sample_genesis_ledger` share storage, so each test should use a distinct program name.
+    pub(crate) fn deploy_public_token_program(
+        ledger: &mut CurrentLedger,
+        private_key: &PrivateKey<CurrentNetwork>,
+        program_name: &str,
+        rng: &mut TestRng,
+    ) -> Program<CurrentNetwork> {
+        // Initialize a program with a publicly-owned record.
+        let program = Program::from_str(&format!(
+            r"
+program {program_name}.aleo;
+
+record token:
+    owner as address.public;
+    gates as u64.public;
+    amount as u64.public;
+
+function mint:
+    input r0 as address.public;
+    input r1 as u64.public;
+    cast r0 0u64 r1 into r2 as token.record;
+    output r2 as token.record;"
+        ))
+        .unwrap();
+
+        // Deploy the program.
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        let (_, credits) = ledger.find_records(&view_key, RecordsFilter::Unspent).unwrap().next().unwrap();
+        let transaction = Transaction::deploy(ledger.vm(), private_key, &program, (credits, 10), rng).unwrap();
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        program
+    }
+
+    /// Deploys a program with the given name, whose `mint_public` function increments the `account` mapping
+    /// of the recipient in its finalize scope, and returns the program.
+    /// Note: Ledgers from `sample_genesis_ledger` share storage, so each test should use a distinct program name.
+    pub(crate) fn deploy_public_mapping_program(
+        ledger: &mut CurrentLedger,
+        private_key: &PrivateKey<CurrentNetwork>,
+        program_name: &str,
+        rng: &mut TestRng,
+    ) -> Program<CurrentNetwork> {
+        // Initialize a program with a public mapping.
+        let program = Program::from_str(&format!(
+            r"
+program {program_name}.aleo;
+
+mapping account:
+    key owner as address.public;
+    value amount as u64.public;
+
+function mint_public:
+    input r0 as address.public;
+    input r1 as u64.public;
+    finalize r0 r1;
+
+finalize mint_public:
+    input r0 as address.public;
+    input r1 as u64.public;
+    increment account[r0] by r1;"
+        ))
+        .unwrap();
+
+        // Deploy the program.
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        let (_, credits) = ledger.find_records(&view_key, RecordsFilter::Unspent).unwrap().next().unwrap();
+        let transaction = Transaction::deploy(ledger.vm(), private_key, &program, (credits, 10), rng).unwrap();
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        program
+    }
+
+    /// Returns a transaction that increments the `account` mapping of the recipient by the given amount,
+    /// using a program from `deploy_public_mapping_program`.
+    pub(crate) fn sample_mint_public_transaction(
+        ledger: &CurrentLedger,
+        private_key: &PrivateKey<CurrentNetwork>,
+        program: &Program<CurrentNetwork>,
+        recipient: Address<CurrentNetwork>,
+        amount: u64,
+        rng: &mut TestRng,
+    ) -> Transaction<CurrentNetwork> {
+        Transaction::execute(
+            ledger.vm(),
+            private_key,
+            program.id(),
+            Identifier::from_str("mint_public").unwrap(),
+            &[Value::from_str(&recipient.to_string()).unwrap(), Value::from_str(&format!("{amount}u64")).unwrap()],
+            None,
+            rng,
+        )
+        .unwrap()
+    }
+
+    /// Returns a transaction that mints the given amount of tokens to the recipient,
+    /// using a program from `deploy_public_token_program`.
+    pub(crate) fn sample_public_mint_transaction(
+        ledger: &CurrentLedger,
+        private_key: &PrivateKey<CurrentNetwork>,
+        program: &Program<CurrentNetwork>,
+        recipient: Address<CurrentNetwork>,
+        amount: u64,
+        rng: &mut TestRng,
+    ) -> Transaction<CurrentNetwork> {
+        Transaction::execute(
+            ledger.vm(),
+            private_key,
+            program.id(),
+            Identifier::from_str("mint").unwrap(),
+            &[Value::from_str(&recipient.to_string()).unwrap(), Value::from_str(&format!("{amount}u64")).unwrap()],
+            None,
+            rng,
+        )
+        .unwrap()
     }
 }
 
@@ -969,6 +2811,32 @@ mod tests {
         assert_eq!(ledger.latest_block().unwrap(), genesis);
     }
 
+    #[test]
+    fn test_block_tree_depth() {
+        // Ensure the block tree depth matches the network.
+        assert_eq!(CurrentNetwork::BLOCKS_DEPTH, BLOCKS_DEPTH);
+        // Ensure a new block tree is empty.
+        let block_tree = CurrentLedger::new_block_tree().unwrap();
+        assert!(block_tree.leaf_hashes().unwrap().is_empty());
+        assert_eq!(*block_tree.root(), *CurrentNetwork::merkle_tree_bhp::<BLOCKS_DEPTH>(&[]).unwrap().root());
+    }
+
+    #[test]
+    fn test_check_genesis_hash() {
+        let rng = &mut TestRng::default();
+
+        // Load the genesis block.
+        let genesis = Block::from_bytes_le(GenesisBytes::load_bytes()).unwrap();
+        // Ensure the genesis block hash is correct.
+        assert!(CurrentLedger::check_genesis_hash(&genesis).is_ok());
+        assert_eq!(Some(genesis.hash().to_string().as_str()), CurrentNetwork::GENESIS_HASH);
+
+        // Sample a different genesis block.
+        let genesis = crate::ledger::test_helpers::sample_genesis_block(rng);
+        // Ensure the genesis block hash is rejected.
+        assert!(CurrentLedger::check_genesis_hash(&genesis).is_err());
+    }
+
     #[test]
     fn test_from() {
         // Load the genesis block.
@@ -1011,6 +2879,137 @@ mod tests {
         let _state_path = ledger.to_state_path(commitment).unwrap();
     }
 
+    #[test]
+    fn test_state_path_verify() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the ledger with the genesis block.
+        let ledger = CurrentLedger::new(None).unwrap();
+        let genesis = ledger.get_block(0).unwrap();
+
+        // Construct the state path, and transfer it to a verifier without a ledger.
+        let commitment = genesis.commitments().next().unwrap();
+        let state_path = ledger.to_state_path(commitment).unwrap();
+        let state_path = StatePath::<CurrentNetwork>::from_bytes_le(&state_path.to_bytes_le().unwrap()).unwrap();
+
+        // Ensure the state path verifies against the state root of the ledger.
+        assert!(state_path.verify(ledger.latest_state_root()).unwrap());
+        // Ensure the state path does not verify against a different state root.
+        assert!(!state_path.verify(&Field::rand(rng)).unwrap());
+    }
+
+    #[test]
+    fn test_state_path_accessors() {
+        // Initialize the ledger with the genesis block.
+        let ledger = CurrentLedger::new(None).unwrap();
+        let genesis = ledger.get_block(0).unwrap();
+
+        // Construct the state path for a commitment.
+        let commitment = genesis.commitments().next().unwrap();
+        let state_path = ledger.to_state_path(commitment).unwrap();
+        assert!(state_path.verify(ledger.latest_state_root()).unwrap());
+
+        // Retrieve the transaction and transition that produced the commitment.
+        let transaction = genesis.transactions().values().find(|tx| tx.commitments().any(|c| c == commitment)).unwrap();
+        let transition = transaction.transitions().find(|t| t.commitments().any(|c| c == commitment)).unwrap();
+
+        // Ensure the accessors match the block.
+        assert_eq!(*state_path.state_root(), *ledger.latest_state_root());
+        assert_eq!(state_path.block_hash(), genesis.hash());
+        assert_eq!(state_path.previous_block_hash(), genesis.previous_hash());
+        assert_eq!(*state_path.header_root(), genesis.header().to_root().unwrap());
+        assert_eq!(state_path.header_leaf().id(), *genesis.transactions_root());
+        assert_eq!(state_path.block_path(), &ledger.block_tree().prove(0, &genesis.hash().to_bits_le()).unwrap());
+
+        // Ensure the accessors match the transaction and transition.
+        assert_eq!(state_path.transaction_id(), &transaction.id());
+        assert_eq!(state_path.transaction_leaf().id(), **transition.id());
+        assert_eq!(state_path.transition_leaf(), &transition.to_leaf(commitment, false).unwrap());
+        assert_eq!(state_path.transition_leaf().id(), *commitment);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_state_path_verify_against_tree() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        let stale_tree = ledger.block_tree().clone();
+
+        // Add a block with a transaction.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Construct the state path for a commitment in the block.
+        let commitment = next_block.commitments().next().unwrap();
+        let state_path = ledger.to_state_path(commitment).unwrap();
+
+        // Ensure the state path verifies against the tree it was generated from.
+        assert!(state_path.verify_against_tree(ledger.block_tree()).unwrap());
+        // Ensure the state path fails against a stale tree, without the block.
+        assert!(!state_path.verify_against_tree(&stale_tree).unwrap());
+
+        // Ensure the state path verifies against a later tree, which still contains the block.
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        assert_ne!(*state_path.state_root(), *ledger.latest_state_root());
+        assert!(state_path.verify_against_tree(ledger.block_tree()).unwrap());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_to_state_path_checked() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis ledger.
+        let ledger = test_helpers::sample_new_genesis_ledger(rng);
+        let genesis = ledger.get_block(0).unwrap();
+
+        // Ensure the state path is found for an existing commitment.
+        let commitment = genesis.commitments().next().unwrap();
+        let state_path = ledger.to_state_path_checked(commitment).unwrap();
+        assert!(state_path == Some(ledger.to_state_path(commitment).unwrap()));
+
+        // Ensure a missing commitment is reported as not found.
+        let missing = Field::<CurrentNetwork>::rand(rng);
+        assert!(ledger.to_state_path_checked(&missing).unwrap().is_none());
+        assert!(ledger.to_state_path(&missing).is_err());
+
+        // Store a transaction outside of any block, to simulate a corrupt ledger.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.transactions.insert(&transaction).unwrap();
+        let commitment = transaction.commitments().next().unwrap();
+        assert!(ledger.contains_commitment(commitment).unwrap());
+
+        // Ensure the commitment without a block is reported as an error.
+        assert!(ledger.to_state_path_checked(commitment).is_err());
+    }
+
+    #[test]
+    fn test_transaction_inclusion_proof() {
+        // Initialize the ledger with the genesis block.
+        let ledger = CurrentLedger::new(None).unwrap();
+        // Retrieve the genesis block.
+        let genesis = ledger.get_block(0).unwrap();
+
+        // Construct the transaction inclusion proof.
+        let transaction_id = genesis.transaction_ids().next().unwrap();
+        let proof = ledger.to_transaction_inclusion_proof(transaction_id).unwrap();
+        assert!(proof.verify().is_ok());
+        assert_eq!(proof.transaction_id(), transaction_id);
+        assert_eq!(proof.block_hash(), genesis.hash());
+        assert_eq!(*proof.state_root(), *ledger.latest_state_root());
+
+        // Ensure a proof cannot be constructed for an unknown transaction.
+        let unknown_id = Field::<CurrentNetwork>::from_u64(1234).into();
+        assert!(ledger.to_transaction_inclusion_proof(&unknown_id).is_err());
+    }
+
     #[test]
     #[traced_test]
     fn test_ledger_deploy() {
@@ -1117,7 +3116,7 @@ mod tests {
                 // Add the transaction to the memory pool.
                 ledger.add_to_memory_pool(transaction).unwrap();
             }
-            assert_eq!(ledger.memory_pool().len(), 1 << (height - 1));
+            assert_eq!(ledger.memory_pool_len(), 1 << (height - 1));
 
             // Propose the next block.
             let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
@@ -1128,4 +3127,1968 @@ mod tests {
             assert_eq!(ledger.latest_hash(), next_block.hash());
         }
     }
+
+    #[test]
+    #[traced_test]
+    fn test_add_next_block_with_path() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add a transaction to the memory pool.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+
+        // Propose the next block.
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+
+        // Add the next block, and retrieve its block path.
+        let block_path = ledger.add_next_block_with_path(&next_block).unwrap();
+        assert_eq!(ledger.latest_height(), 1);
+
+        // Ensure the block path verifies against the latest state root.
+        let state_root = *ledger.latest_state_root();
+        assert!(CurrentNetwork::verify_merkle_path_bhp(&block_path, &state_root, &next_block.hash().to_bits_le()));
+        // Ensure the block path matches the one proven from the block tree.
+        let candidate = ledger.block_tree().prove(1, &next_block.hash().to_bits_le()).unwrap();
+        assert_eq!(block_path, candidate);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_finalize_block_error() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add a block with a deployment.
+        let deployment = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+        ledger.add_to_memory_pool(deployment.clone()).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Construct a block with an execution, followed by a duplicate deployment.
+        let execution = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(execution.clone()).unwrap();
+        let (header, _) = ledger.build_next_header().unwrap();
+        let transactions = Transactions::from(&[execution, deployment.clone()]);
+        let block = Block::new(&private_key, ledger.latest_hash(), header, transactions, rng).unwrap();
+
+        // Ensure the error identifies the duplicate deployment, and its index in the block.
+        let error = CurrentLedger::finalize_block(&mut ledger.vm.clone(), &block).unwrap_err();
+        let expected = format!("Failed to finalize transaction '{}' (index 1) in block 2", deployment.id());
+        assert!(error.to_string().starts_with(&expected), "{error}");
+
+        // Ensure the block is rejected.
+        assert!(ledger.add_next_block(&block).is_err());
+        assert_eq!(ledger.latest_height(), 1);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_apply_block_stream() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Construct the next 3 blocks, each splitting an unspent record.
+        let mut blocks = Vec::new();
+        for _ in 0..3 {
+            let (_, _, next_block) = test_helpers::add_split_block(&mut ledger, &private_key, rng);
+            blocks.push(next_block);
+        }
+
+        // Construct an invalid block, which forks from the genesis block.
+        let mut fork_ledger = test_helpers::sample_new_genesis_ledger(rng);
+        let transaction = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+        fork_ledger.add_to_memory_pool(transaction).unwrap();
+        let fork_block = fork_ledger.propose_next_block(&private_key, rng).unwrap();
+
+        // Apply a valid prefix, followed by an invalid block and a trailing valid block.
+        // Note: A block that already exists in the ledger is skipped, rather than treated as invalid.
+        let mut other_ledger = test_helpers::sample_new_genesis_ledger(rng);
+        let stream = [blocks[0].clone(), blocks[1].clone(), blocks[0].clone(), fork_block, blocks[2].clone()];
+        assert_eq!(other_ledger.apply_block_stream(stream).unwrap(), 2);
+        assert_eq!(other_ledger.latest_height(), 2);
+        assert_eq!(other_ledger.latest_hash(), blocks[1].hash());
+        assert!(!other_ledger.contains_block_hash(&blocks[2].hash()).unwrap());
+
+        // Ensure the ledger can resume from the last applied block.
+        assert_eq!(other_ledger.apply_block_stream([blocks[2].clone()]).unwrap(), 3);
+        assert_eq!(other_ledger.latest_hash(), ledger.latest_hash());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_build_next_header() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add a transaction to the memory pool.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+
+        // Build the next header, and compute the expected block hash.
+        let (header, transactions) = ledger.build_next_header().unwrap();
+        let block_hash = ledger.compute_next_block_hash(&header).unwrap();
+
+        // Sign the next block.
+        let next_block = Block::new(&private_key, ledger.latest_hash(), header, transactions, rng).unwrap();
+        assert_eq!(next_block.hash(), block_hash);
+        assert_eq!(next_block.header(), &header);
+
+        // Ensure the next block matches the proposed block, up to the timestamp and signature.
+        let proposed_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        assert_eq!(proposed_block.previous_hash(), next_block.previous_hash());
+        assert_eq!(proposed_block.transactions(), next_block.transactions());
+        assert_eq!(proposed_block.previous_state_root(), next_block.previous_state_root());
+        assert_eq!(proposed_block.transactions_root(), next_block.transactions_root());
+        assert_eq!(proposed_block.height(), next_block.height());
+        assert_eq!(proposed_block.round(), next_block.round());
+
+        // Ensure the signed block is a valid next block.
+        ledger.add_next_block(&next_block).unwrap();
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_propose_empty_block() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        assert_eq!(ledger.memory_pool_len(), 0);
+
+        // Propose the next block from the empty memory pool.
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        assert!(next_block.transactions().is_empty());
+
+        // Ensure the transactions root is the root of the empty Merkle tree.
+        let empty_root = Transactions::<CurrentNetwork>::from(&[]).to_root().unwrap();
+        assert_eq!(*next_block.transactions_root(), empty_root);
+        assert_eq!(Block::read_le(&next_block.to_bytes_le().unwrap()[..]).unwrap(), next_block);
+
+        // Ensure the empty block is a valid next block.
+        ledger.check_next_block(&next_block).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        assert_eq!(ledger.latest_height(), 1);
+        assert_eq!(ledger.get_block(1).unwrap(), next_block);
+    }
+
+    #[test]
+    fn test_anchor_block_height() {
+        let interval = CurrentNetwork::ANCHOR_BLOCK_INTERVAL;
+
+        // Ensure the anchor block is the last retarget boundary preceding the given height.
+        assert_eq!(CurrentLedger::anchor_block_height(0), 0);
+        assert_eq!(CurrentLedger::anchor_block_height(1), 0);
+        assert_eq!(CurrentLedger::anchor_block_height(interval), 0);
+        assert_eq!(CurrentLedger::anchor_block_height(interval + 1), interval);
+        assert_eq!(CurrentLedger::anchor_block_height(2 * interval), interval);
+        assert_eq!(CurrentLedger::anchor_block_height(2 * interval + 5), 2 * interval);
+        assert_eq!(CurrentLedger::anchor_block_height(u32::MAX), (u32::MAX - 1) / interval * interval);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_anchor_block_header() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis ledger.
+        let ledger = test_helpers::sample_genesis_ledger(rng);
+        let genesis_header = *ledger.get_block(0).unwrap().header();
+
+        // Ensure the genesis block is the anchor for the first retarget interval.
+        assert_eq!(ledger.anchor_block_header(1).unwrap(), genesis_header);
+        assert_eq!(ledger.anchor_block_header(CurrentNetwork::ANCHOR_BLOCK_INTERVAL).unwrap(), genesis_header);
+        // Ensure a missing anchor block is not found.
+        assert!(ledger.anchor_block_header(CurrentNetwork::ANCHOR_BLOCK_INTERVAL + 1).is_err());
+    }
+
+    #[test]
+    fn test_retarget_block_time() {
+        let rng = &mut TestRng::default();
+
+        // Construct an anchor block header at height 256.
+        let (target, timestamp, height) = (1u64 << 40, 1_000_000i64, CurrentNetwork::ANCHOR_BLOCK_INTERVAL);
+        let metadata = Metadata::new(CurrentNetwork::ID, height as u64, height, target, target, timestamp).unwrap();
+        let header = Header::from(Field::rand(rng), Field::rand(rng), metadata).unwrap();
+
+        // Ensure the targets are retargeted with the network block time.
+        let block_time = CurrentNetwork::BLOCK_TIME_SECONDS;
+        let (next_height, num_blocks) = (height + 100, 100i64);
+        for elapsed in [num_blocks * block_time, num_blocks * block_time / 2, num_blocks * block_time * 2] {
+            let expected =
+                CurrentLedger::retarget(target, timestamp, height, timestamp + elapsed, next_height, block_time);
+            assert_eq!(CurrentLedger::compute_proof_target(&header, timestamp + elapsed, next_height), expected);
+            assert_eq!(CurrentLedger::compute_coinbase_target(&header, timestamp + elapsed, next_height), expected);
+        }
+
+        // Ensure blocks at the block time preserve the target, and faster or slower blocks lower or raise the target.
+        let on_time = timestamp + num_blocks * block_time;
+        assert_eq!(CurrentLedger::compute_proof_target(&header, on_time, next_height), target);
+        assert_eq!(
+            CurrentLedger::compute_proof_target(&header, on_time - num_blocks * block_time / 2, next_height),
+            target / 2
+        );
+        assert_eq!(
+            CurrentLedger::compute_proof_target(&header, on_time + num_blocks * block_time, next_height),
+            target * 2
+        );
+
+        // Ensure a longer block time computes a lower target for the same timestamps.
+        assert_eq!(
+            CurrentLedger::retarget(target, timestamp, height, on_time, next_height, 2 * block_time),
+            target / 2
+        );
+        // Ensure a longer block time preserves the target for blocks at that block time.
+        let slow_on_time = timestamp + num_blocks * 2 * block_time;
+        assert_eq!(
+            CurrentLedger::retarget(target, timestamp, height, slow_on_time, next_height, 2 * block_time),
+            target
+        );
+        // Ensure the adjustment is bounded.
+        assert_eq!(CurrentLedger::retarget(target, timestamp, height, timestamp, next_height, block_time), target / 4);
+        assert_eq!(CurrentLedger::retarget(u64::MAX, 0, 0, i64::MAX, 1, block_time), u64::MAX);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_cumulative_proof_target() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample two genesis ledgers, with separate storage.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        let mut other_ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Ensure the cumulative proof target includes the genesis block.
+        let genesis_weight = CurrentLedger::compute_block_weight(ledger.get_header(0).unwrap().proof_target());
+        assert_eq!(ledger.cumulative_proof_target(), genesis_weight);
+
+        // Add the next block, and ensure the cumulative proof target increases.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction.clone()).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        let weight = CurrentLedger::compute_block_weight(next_block.proof_target());
+        assert!(weight > 0);
+        assert_eq!(ledger.cumulative_proof_target(), genesis_weight + weight);
+
+        // Constructs a fork block at height 1 with the given proof target.
+        let mut sample_fork_block = |proof_target: u64| {
+            other_ledger.add_to_memory_pool(transaction.clone()).unwrap();
+            let (header, transactions) = other_ledger.build_next_header().unwrap();
+            let metadata = Metadata::new(
+                header.network(),
+                header.round(),
+                1,
+                header.coinbase_target(),
+                proof_target,
+                header.timestamp(),
+            )
+            .unwrap();
+            let header = Header::from(*header.previous_state_root(), *header.transactions_root(), metadata).unwrap();
+            other_ledger.replace_memory_pool([], true).unwrap();
+            Block::new(&private_key, other_ledger.latest_hash(), header, transactions, rng).unwrap()
+        };
+
+        // Ensure a fork with an equal weight is not preferred.
+        let fork_block = sample_fork_block(next_block.proof_target());
+        assert!(!ledger.is_preferred_fork(&[fork_block]).unwrap());
+
+        // Ensure a harder fork is preferred.
+        let fork_block = sample_fork_block(next_block.proof_target() / 4);
+        assert!(CurrentLedger::compute_block_weight(fork_block.proof_target()) > weight);
+        assert!(ledger.is_preferred_fork(std::slice::from_ref(&fork_block)).unwrap());
+
+        // Ensure the fork must be contiguous.
+        assert!(ledger.is_preferred_fork(&[fork_block.clone(), fork_block]).is_err());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_replace_memory_pool() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Sample a valid transaction, and an invalid transaction that already exists in the ledger.
+        let valid = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        let invalid = ledger.get_block(0).unwrap().transactions().values().next().unwrap().clone();
+        assert!(ledger.check_transaction(&valid).is_ok());
+        assert!(ledger.check_transaction(&invalid).is_err());
+
+        // Ensure a strict replacement with an invalid transaction leaves the memory pool unchanged.
+        ledger.add_to_memory_pool(valid.clone()).unwrap();
+        assert!(ledger.replace_memory_pool([invalid.clone(), valid.clone()], true).is_err());
+        assert_eq!(ledger.memory_pool().keys().map(|id| *id).collect::<Vec<_>>(), vec![valid.id()]);
+
+        // Ensure a strict replacement with only valid transactions succeeds.
+        ledger.replace_memory_pool([], true).unwrap();
+        assert_eq!(ledger.memory_pool_len(), 0);
+        ledger.replace_memory_pool([valid.clone()], true).unwrap();
+        assert_eq!(ledger.memory_pool().keys().map(|id| *id).collect::<Vec<_>>(), vec![valid.id()]);
+
+        // Ensure a lenient replacement skips the invalid and duplicate transactions.
+        ledger.replace_memory_pool([invalid, valid.clone(), valid.clone()], false).unwrap();
+        assert_eq!(ledger.memory_pool().keys().map(|id| *id).collect::<Vec<_>>(), vec![valid.id()]);
+
+        // Sample two transactions that spend the same record.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        let (_, record) = test_helpers::sample_unspent_record(&ledger, &view_key);
+        let mut pay_fee = |fee: u64| {
+            Transaction::execute(
+                ledger.vm(),
+                &private_key,
+                &ProgramID::from_str("credits.aleo").unwrap(),
+                Identifier::from_str("fee").unwrap(),
+                &[Value::Record(record.clone()), Value::from_str(&format!("{fee}u64")).unwrap()],
+                None,
+                rng,
+            )
+            .unwrap()
+        };
+        let (first, second) = (pay_fee(1), pay_fee(2));
+
+        // Ensure a strict replacement with transactions that spend the same serial number is rejected.
+        assert!(ledger.replace_memory_pool([first.clone(), second.clone()], true).is_err());
+        assert_eq!(ledger.memory_pool().keys().map(|id| *id).collect::<Vec<_>>(), vec![valid.id()]);
+
+        // Ensure a lenient replacement skips the later transaction that spends the same serial number.
+        ledger.replace_memory_pool([first.clone(), second.clone()], false).unwrap();
+        assert_eq!(ledger.memory_pool().keys().map(|id| *id).collect::<Vec<_>>(), vec![first.id()]);
+
+        // Ensure a replacement enforces the memory pool policy.
+        ledger.set_min_fee(2);
+        let error = ledger.replace_memory_pool([first.clone()], true).unwrap_err();
+        assert!(error.to_string().contains("below the minimum fee of 2 gates"), "{error}");
+        assert_eq!(ledger.memory_pool().keys().map(|id| *id).collect::<Vec<_>>(), vec![first.id()]);
+        ledger.replace_memory_pool([first, second.clone()], false).unwrap();
+        assert_eq!(ledger.memory_pool().keys().map(|id| *id).collect::<Vec<_>>(), vec![second.id()]);
+        ledger.set_min_fee(0);
+
+        // Ensure a replacement enforces the memory pool rate limit, and a rejected replacement does not consume it.
+        let limit = RateLimit::new(1, std::time::Duration::from_secs(3600), RateLimitKey::Global).unwrap();
+        ledger.set_memory_pool_rate_limit(Some(limit));
+        let error = ledger.replace_memory_pool([second.clone(), valid.clone()], true).unwrap_err();
+        assert!(error.to_string().contains(&MemoryPoolRateLimited.to_string()), "{error}");
+        ledger.replace_memory_pool([valid.clone()], true).unwrap();
+        assert_eq!(ledger.memory_pool().keys().map(|id| *id).collect::<Vec<_>>(), vec![valid.id()]);
+        assert!(ledger.replace_memory_pool([second], true).is_err());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_memory_pool_diff() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        let coinbase = ledger.get_coinbase_transaction(0).unwrap().unwrap();
+
+        // Propose a block with an execution and a deployment transaction.
+        let execution = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        let deployment = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+        ledger.add_to_memory_pool(execution.clone()).unwrap();
+        ledger.add_to_memory_pool(deployment.clone()).unwrap();
+        let block = ledger.propose_next_block(&private_key, rng).unwrap();
+
+        // Ensure the memory pool matches the proposed block.
+        assert_eq!(ledger.memory_pool_diff(&block), (vec![], vec![]));
+
+        // Replace the execution in the memory pool with a transaction that is not in the block.
+        ledger.memory_pool.remove(&execution.id()).unwrap();
+        ledger.memory_pool.insert(coinbase.id(), coinbase.clone()).unwrap();
+
+        // Ensure the differences are reported in both directions.
+        assert_eq!(ledger.memory_pool_diff(&block), (vec![coinbase.id()], vec![execution.id()]));
+
+        // Ensure an empty memory pool is missing every block transaction.
+        ledger.clear_memory_pool().unwrap();
+        let (not_in_block, not_in_pool) = ledger.memory_pool_diff(&block);
+        assert!(not_in_block.is_empty());
+        assert_eq!(not_in_pool, block.transaction_ids().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_memory_pool_map() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add a deployment and an execution transaction to the memory pool.
+        let deployment = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+        let execution = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(deployment.clone()).unwrap();
+        ledger.add_to_memory_pool(execution.clone()).unwrap();
+
+        // Ensure the memory pool preserves the order of insertion, and rejects duplicates.
+        let expected = vec![(deployment.id(), deployment.clone()), (execution.id(), execution.clone())];
+        let entries = |memory_pool: &MemoryMap<_, Transaction<CurrentNetwork>>| {
+            memory_pool.iter().map(|(id, transaction)| (*id, transaction.into_owned())).collect::<Vec<_>>()
+        };
+        assert_eq!(entries(ledger.memory_pool()), expected);
+        assert_eq!(ledger.memory_pool_len(), 2);
+        assert!(ledger.add_to_memory_pool(execution.clone()).is_err());
+        assert_eq!(ledger.memory_pool_len(), 2);
+
+        // Copy the memory pool, as a persisted memory pool from before the next block.
+        let snapshot = MemoryMap::default();
+        for (transaction_id, transaction) in &expected {
+            snapshot.insert(*transaction_id, transaction.clone()).unwrap();
+        }
+
+        // Ensure a ledger loaded with the memory pool recovers the pending transactions.
+        let loaded = CurrentLedger::from_with_memory_pool(
+            ledger.blocks.clone(),
+            ledger.vm.program_store().clone(),
+            ledger.memory_pool().clone(),
+        )
+        .unwrap();
+        assert_eq!(entries(loaded.memory_pool()), expected);
+        assert_eq!(loaded.build_next_header().unwrap().1, ledger.build_next_header().unwrap().1);
+
+        // Add the next block, and ensure the memory pool is cleared of its transactions.
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        assert_eq!(ledger.memory_pool_len(), 0);
+
+        // Ensure a ledger loaded with the stale memory pool removes the transactions already in the ledger.
+        let loaded =
+            CurrentLedger::from_with_memory_pool(ledger.blocks.clone(), ledger.vm.program_store().clone(), snapshot)
+                .unwrap();
+        assert_eq!(loaded.memory_pool_len(), 0);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_clear_memory_pool() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Ensure clearing an empty memory pool removes nothing.
+        assert_eq!(ledger.clear_memory_pool().unwrap(), 0);
+
+        // Add transactions to the memory pool.
+        let execution = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        let deployment = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+        ledger.add_to_memory_pool(execution.clone()).unwrap();
+        ledger.add_to_memory_pool(deployment).unwrap();
+        assert_eq!(ledger.memory_pool_len(), 2);
+
+        // Ensure the memory pool is cleared.
+        assert_eq!(ledger.clear_memory_pool().unwrap(), 2);
+        assert_eq!(ledger.memory_pool_len(), 0);
+        assert_eq!(ledger.clear_memory_pool().unwrap(), 0);
+
+        // Ensure a cleared transaction can be added again.
+        ledger.add_to_memory_pool(execution.clone()).unwrap();
+        assert_eq!(ledger.memory_pool().keys().map(|id| *id).collect::<Vec<_>>(), vec![execution.id()]);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_add_to_memory_pool_logs_decisions() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Ensure the admission is logged.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction.clone()).unwrap();
+        assert!(logs_contain("Admitted a transaction to the memory pool"));
+        assert!(!logs_contain("Rejected a transaction from the memory pool"));
+
+        // Ensure the rejection of a duplicate transaction is logged, with the transaction ID and reason.
+        assert!(ledger.add_to_memory_pool(transaction.clone()).is_err());
+        assert!(logs_contain("Rejected a transaction from the memory pool"));
+        assert!(logs_contain(&format!("transaction_id={}", transaction.id())));
+        assert!(logs_contain("already exists in the memory pool"));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_add_to_memory_pool_rate_limit() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        // Ensure the rate limit is disabled by default.
+        assert_eq!(ledger.memory_pool_rate_limit(), None);
+
+        // Limit the memory pool to one submission per hour.
+        let limit = RateLimit::new(1, std::time::Duration::from_secs(3600), RateLimitKey::Global).unwrap();
+        ledger.set_memory_pool_rate_limit(Some(limit));
+        assert_eq!(ledger.memory_pool_rate_limit(), Some(limit));
+
+        // Ensure an invalid submission is rejected, without consuming the rate limit.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.set_min_fee(u64::MAX);
+        let error = ledger.add_to_memory_pool(transaction.clone()).unwrap_err();
+        assert!(error.downcast_ref::<MemoryPoolRateLimited>().is_none());
+        ledger.set_min_fee(0);
+
+        // Ensure the first valid submission is admitted.
+        ledger.add_to_memory_pool(transaction).unwrap();
+
+        // Ensure the excess submission is rejected.
+        let transaction = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+        let error = ledger.add_to_memory_pool(transaction.clone()).unwrap_err();
+        assert_eq!(error.downcast_ref::<MemoryPoolRateLimited>(), Some(&MemoryPoolRateLimited));
+        assert_eq!(ledger.memory_pool_len(), 1);
+
+        // Ensure the submission is admitted once the rate limit is disabled.
+        ledger.set_memory_pool_rate_limit(None);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        assert_eq!(ledger.memory_pool_len(), 2);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_add_to_memory_pool_replace_by_fee() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key and view key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        ledger.set_fee_bump_threshold(5);
+
+        // Fetch an unspent record.
+        let (_, record) = test_helpers::sample_unspent_record(&ledger, &view_key);
+
+        // Constructs a transaction that spends the record with the given fee.
+        let mut pay_fee = |fee: u64| {
+            Transaction::execute(
+                ledger.vm(),
+                &private_key,
+                &ProgramID::from_str("credits.aleo").unwrap(),
+                Identifier::from_str("fee").unwrap(),
+                &[Value::Record(record.clone()), Value::from_str(&format!("{fee}u64")).unwrap()],
+                None,
+                rng,
+            )
+            .unwrap()
+        };
+        let low_fee = pay_fee(1);
+        let insufficient_fee = pay_fee(6);
+        let high_fee = pay_fee(10);
+
+        // Add the low-fee transaction to the memory pool.
+        assert!(ledger.add_to_memory_pool(low_fee.clone()).unwrap().is_none());
+
+        // Ensure a conflicting transaction that does not exceed the fee bump threshold is rejected.
+        assert!(ledger.add_to_memory_pool(insufficient_fee).is_err());
+        assert_eq!(ledger.memory_pool().keys().map(|id| *id).collect::<Vec<_>>(), vec![low_fee.id()]);
+
+        // Ensure a conflicting transaction with a sufficiently higher fee replaces the low-fee transaction.
+        let replaced = ledger.add_to_memory_pool(high_fee.clone()).unwrap();
+        assert_eq!(replaced, Some(low_fee.clone()));
+        assert_eq!(ledger.memory_pool().keys().map(|id| *id).collect::<Vec<_>>(), vec![high_fee.id()]);
+
+        // Ensure the replaced transaction cannot displace the higher-fee transaction.
+        assert!(ledger.add_to_memory_pool(low_fee).is_err());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_add_many_to_memory_pool() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key and view key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Fetch an unspent record.
+        let (_, record) = test_helpers::sample_unspent_record(&ledger, &view_key);
+
+        // Constructs a transaction that spends the record with the given fee.
+        let mut pay_fee = |fee: u64| {
+            Transaction::execute(
+                ledger.vm(),
+                &private_key,
+                &ProgramID::from_str("credits.aleo").unwrap(),
+                Identifier::from_str("fee").unwrap(),
+                &[Value::Record(record.clone()), Value::from_str(&format!("{fee}u64")).unwrap()],
+                None,
+                rng,
+            )
+            .unwrap()
+        };
+        let low_fee = pay_fee(1);
+        let high_fee = pay_fee(10);
+
+        // Ensure a later conflicting transaction in the batch is rejected, even with a higher fee.
+        let results = ledger.add_many_to_memory_pool(vec![low_fee.clone(), high_fee.clone(), low_fee.clone()]);
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        let error = results[1].as_ref().unwrap_err().to_string();
+        assert!(error.contains(&format!("conflicts with transaction '{}' earlier in the batch", low_fee.id())));
+        let error = results[2].as_ref().unwrap_err().to_string();
+        assert!(error.contains("already exists in the memory pool"));
+        assert_eq!(ledger.memory_pool().keys().map(|id| *id).collect::<Vec<_>>(), vec![low_fee.id()]);
+
+        // Ensure a transaction in a later batch may still replace a conflicting transaction by fee.
+        let results = ledger.add_many_to_memory_pool(vec![high_fee.clone()]);
+        assert!(results[0].is_ok());
+        assert_eq!(ledger.memory_pool().keys().map(|id| *id).collect::<Vec<_>>(), vec![high_fee.id()]);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_add_to_memory_pool_min_fee() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key and view key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        assert_eq!(ledger.min_fee(), 0);
+        ledger.set_min_fee(5);
+        assert_eq!(ledger.min_fee(), 5);
+
+        // Fetch an unspent record.
+        let (_, record) = test_helpers::sample_unspent_record(&ledger, &view_key);
+
+        // Constructs a transaction that spends the record with the given fee.
+        let mut pay_fee = |fee: u64| {
+            Transaction::execute(
+                ledger.vm(),
+                &private_key,
+                &ProgramID::from_str("credits.aleo").unwrap(),
+                Identifier::from_str("fee").unwrap(),
+                &[Value::Record(record.clone()), Value::from_str(&format!("{fee}u64")).unwrap()],
+                None,
+                rng,
+            )
+            .unwrap()
+        };
+        let low_fee = pay_fee(4);
+        let sufficient_fee = pay_fee(5);
+
+        // Ensure a transaction below the minimum fee is rejected.
+        let error = ledger.add_to_memory_pool(low_fee.clone()).unwrap_err();
+        assert!(error.to_string().contains("below the minimum fee of 5 gates"), "{error}");
+        assert_eq!(ledger.memory_pool_len(), 0);
+
+        // Ensure a transaction that pays the minimum fee is accepted.
+        ledger.add_to_memory_pool(sufficient_fee.clone()).unwrap();
+        assert_eq!(ledger.memory_pool().keys().map(|id| *id).collect::<Vec<_>>(), vec![sufficient_fee.id()]);
+
+        // Ensure the minimum fee can be disabled.
+        ledger.set_min_fee(0);
+        ledger.clear_memory_pool().unwrap();
+        ledger.add_to_memory_pool(low_fee).unwrap();
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_propose_next_block_orders_dependencies() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key and view key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Fetch an unspent record.
+        let (_, record) = test_helpers::sample_unspent_record(&ledger, &view_key);
+
+        // Construct a transaction that produces a record, and a transaction that spends the produced record.
+        let producer = test_helpers::sample_split_transaction(&ledger, &private_key, record.clone(), rng);
+        let (_, output_record) = producer.transitions().flat_map(Transition::output_records).next().unwrap();
+        let consumer = test_helpers::sample_split_transaction(
+            &ledger,
+            &private_key,
+            output_record.decrypt(&view_key).unwrap(),
+            rng,
+        );
+
+        // Ensure the consumer is rejected before the producer is in the memory pool.
+        assert!(ledger.add_to_memory_pool(consumer.clone()).is_err());
+
+        // Add the producer and the consumer to the memory pool.
+        ledger.add_to_memory_pool(producer.clone()).unwrap();
+        ledger.add_to_memory_pool(consumer.clone()).unwrap();
+
+        // Ensure the producer precedes the consumer in the proposed block.
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        let transaction_ids = next_block.transaction_ids().copied().collect::<Vec<_>>();
+        assert_eq!(transaction_ids, vec![producer.id(), consumer.id()]);
+        // Ensure the proposed block is valid.
+        ledger.check_next_block(&next_block).unwrap();
+
+        // Construct a higher-fee transaction that spends the same record as the producer.
+        let conflict = Transaction::execute(
+            ledger.vm(),
+            &private_key,
+            &ProgramID::from_str("credits.aleo").unwrap(),
+            Identifier::from_str("fee").unwrap(),
+            &[Value::Record(record), Value::from_str("1u64").unwrap()],
+            None,
+            rng,
+        )
+        .unwrap();
+        ledger.memory_pool.insert(conflict.id(), conflict.clone()).unwrap();
+
+        // Ensure the consumer is omitted when its producer is not selected.
+        let conflict_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        assert_eq!(conflict_block.transaction_ids().copied().collect::<Vec<_>>(), vec![conflict.id()]);
+
+        // Add the block with the producer and the consumer to the ledger.
+        ledger.add_next_block(&next_block).unwrap();
+        assert_eq!(ledger.latest_height(), 1);
+        assert!(ledger.contains_transaction_id(&consumer.id()).unwrap());
+        // Ensure the memory pool is cleared of the block transactions, and the conflicting transaction.
+        assert_eq!(ledger.memory_pool_len(), 0);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_encrypted_memory_pool() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Encrypt a transaction with a one-time pad.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        let plaintext = transaction.to_bytes_le().unwrap();
+        let pad = (0..plaintext.len()).map(|_| rng.gen()).collect::<Vec<u8>>();
+        let ciphertext = plaintext.iter().zip(&pad).map(|(byte, key)| byte ^ key).collect::<Vec<u8>>();
+        let decryption_handle: DecryptionHandle<CurrentNetwork> = Arc::new(move |ciphertext: &[u8]| {
+            let plaintext = ciphertext.iter().zip(&pad).map(|(byte, key)| byte ^ key).collect::<Vec<u8>>();
+            Transaction::from_bytes_le(&plaintext)
+        });
+
+        // Ensure an encrypted transaction that does not satisfy the memory pool policy is rejected.
+        ledger.set_min_fee(u64::MAX);
+        let error = ledger.add_encrypted_to_memory_pool(ciphertext.clone(), decryption_handle.clone()).unwrap_err();
+        assert!(error.to_string().contains("below the minimum fee"), "{error}");
+        assert_eq!(ledger.encrypted_memory_pool_len(), 0);
+        ledger.set_min_fee(0);
+        // Ensure an encrypted transaction that exceeds the maximum transaction size is rejected.
+        let oversized = vec![0u8; CurrentNetwork::MAX_TRANSACTION_BYTES + 1];
+        let error = ledger.add_encrypted_to_memory_pool(oversized, decryption_handle.clone()).unwrap_err();
+        assert!(error.to_string().contains("exceeds the maximum"), "{error}");
+
+        // Add the encrypted transaction, and ensure it is not added twice.
+        ledger.add_encrypted_to_memory_pool(ciphertext.clone(), decryption_handle.clone()).unwrap();
+        assert!(ledger.add_encrypted_to_memory_pool(ciphertext, decryption_handle).is_err());
+        // Ensure an encrypted transaction that fails to decrypt is rejected.
+        assert!(ledger
+            .add_encrypted_to_memory_pool(vec![0u8; 32], Arc::new(|_: &[u8]| bail!("Invalid ciphertext")))
+            .is_err());
+        assert_eq!(ledger.memory_pool_len(), 0);
+        assert_eq!(ledger.encrypted_memory_pool_len(), 1);
+
+        // Ensure the proposed block contains the decrypted transaction.
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        assert_eq!(next_block.transaction_ids().collect::<Vec<_>>(), vec![&transaction.id()]);
+
+        // Add the next block, and ensure the encrypted memory pool is cleared.
+        ledger.add_next_block(&next_block).unwrap();
+        assert!(ledger.contains_transaction_id(&transaction.id()).unwrap());
+        assert_eq!(ledger.encrypted_memory_pool_len(), 0);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_subscribe_records() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key and view key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Subscribe to the records of the genesis account, and of another account.
+        let receiver = ledger.subscribe_records(view_key);
+        let other_view_key = ViewKey::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        let other_receiver = ledger.subscribe_records(other_view_key);
+        // Subscribe with a receiver that is dropped.
+        drop(ledger.subscribe_records(view_key));
+
+        // Add a block with a transfer to the genesis account.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction.clone()).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Ensure the genesis account is notified of its records in the new block.
+        let expected = transaction
+            .transitions()
+            .flat_map(Transition::output_records)
+            .filter(|(_, record)| record.is_owner(&view_key))
+            .map(|(_, record)| (1, record.decrypt(&view_key).unwrap()))
+            .collect::<Vec<_>>();
+        assert!(!expected.is_empty());
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), expected);
+
+        // Ensure the other account is not notified.
+        assert!(other_receiver.try_recv().is_err());
+        // Ensure the dropped subscriber is removed.
+        assert_eq!(ledger.record_subscribers.len(), 2);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_check_next_block_orphan() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Construct the next two blocks on a separate ledger.
+        let mut other_ledger = test_helpers::sample_new_genesis_ledger(rng);
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        other_ledger.add_to_memory_pool(transaction).unwrap();
+        let block_1 = other_ledger.propose_next_block(&private_key, rng).unwrap();
+        other_ledger.add_next_block(&block_1).unwrap();
+        let block_2 = other_ledger.propose_next_block(&private_key, rng).unwrap();
+        assert_eq!(block_2.height(), ledger.latest_height() + 2);
+
+        // Ensure the future block is rejected as an orphan block.
+        let error = ledger.check_next_block(&block_2).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<BlockValidationError<CurrentNetwork>>(),
+            Some(&BlockValidationError::Orphan {
+                expected_previous: ledger.latest_hash(),
+                got_previous: block_1.hash()
+            })
+        );
+
+        // Ensure the next block is not an orphan block.
+        assert!(ledger.check_next_block(&block_1).is_ok());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_check_next_block_parallel_large_block() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key and view key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        assert!(ledger.parallel_block_checks());
+
+        // Split the records over a few blocks, and propose a large block that splits all of the records.
+        let mut block = None;
+        for height in 1..5 {
+            let records = ledger
+                .find_records(&view_key, RecordsFilter::Unspent)
+                .unwrap()
+                .filter(|(_, record)| !record.gates().is_zero())
+                .collect::<Vec<_>>();
+            assert_eq!(records.len(), 1 << (height - 1));
+            for (_, record) in records {
+                let transaction = test_helpers::sample_split_transaction(&ledger, &private_key, record, rng);
+                ledger.add_to_memory_pool(transaction).unwrap();
+            }
+            let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+            match height {
+                4 => block = Some(next_block),
+                _ => ledger.add_next_block(&next_block).unwrap(),
+            }
+        }
+        let block = block.unwrap();
+        assert_eq!(block.transactions().len(), 8);
+
+        // Ensure the block is accepted with and without parallelism.
+        for parallel_block_checks in [false, true] {
+            ledger.set_parallel_block_checks(parallel_block_checks);
+            ledger.check_next_block(&block).unwrap();
+        }
+
+        // Construct a large block that repeats a transaction of the latest block, as its last transaction.
+        let latest_transaction = ledger.latest_transactions().unwrap().values().next().unwrap().clone();
+        let transactions =
+            Transactions::from(&block.transactions().values().cloned().chain([latest_transaction]).collect::<Vec<_>>());
+        let metadata = Metadata::new(
+            CurrentNetwork::ID,
+            ledger.latest_round() + 1,
+            ledger.latest_height() + 1,
+            u64::MAX,
+            u64::MAX,
+            ledger.latest_timestamp() + CurrentNetwork::BLOCK_TIME_SECONDS,
+        )
+        .unwrap();
+        let header = Header::from(*ledger.latest_state_root(), transactions.to_root().unwrap(), metadata).unwrap();
+        let invalid_block = Block::new(&private_key, ledger.latest_hash(), header, transactions, rng).unwrap();
+
+        // Ensure the block is rejected with the same error, with and without parallelism.
+        let errors = [false, true].map(|parallel_block_checks| {
+            ledger.set_parallel_block_checks(parallel_block_checks);
+            ledger.check_next_block(&invalid_block).unwrap_err().to_string()
+        });
+        assert_eq!(errors[0], errors[1]);
+        assert!(errors[0].contains("already exists in the ledger"), "{}", errors[0]);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_check_next_block_parallel_same_error() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add a block with an execution and a deployment transaction.
+        let execution = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        let deployment = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+        ledger.add_to_memory_pool(execution.clone()).unwrap();
+        ledger.add_to_memory_pool(deployment.clone()).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Construct a block that repeats both transactions, in reverse order.
+        let transactions = Transactions::from(&[deployment.clone(), execution]);
+        let metadata = Metadata::new(
+            CurrentNetwork::ID,
+            ledger.latest_round() + 1,
+            ledger.latest_height() + 1,
+            u64::MAX,
+            u64::MAX,
+            ledger.latest_timestamp() + CurrentNetwork::BLOCK_TIME_SECONDS,
+        )
+        .unwrap();
+        let header = Header::from(*ledger.latest_state_root(), transactions.to_root().unwrap(), metadata).unwrap();
+        let block = Block::new(&private_key, ledger.latest_hash(), header, transactions, rng).unwrap();
+
+        // Ensure the same error, for the first repeated transaction, is reported with and without parallelism.
+        let errors = [false, true].map(|parallel_block_checks| {
+            ledger.set_parallel_block_checks(parallel_block_checks);
+            ledger.check_next_block(&block).unwrap_err().to_string()
+        });
+        assert_eq!(errors[0], errors[1]);
+        assert_eq!(errors[0], format!("Transaction '{}' already exists in the ledger", deployment.id()));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_add_next_block_orphan_buffer() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger, and enable orphan buffering.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        assert_eq!(ledger.max_orphan_blocks(), 0);
+        ledger.set_max_orphan_blocks(1);
+
+        // Construct the next two blocks on a separate ledger.
+        let mut other_ledger = test_helpers::sample_new_genesis_ledger(rng);
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        other_ledger.add_to_memory_pool(transaction).unwrap();
+        let block_1 = other_ledger.propose_next_block(&private_key, rng).unwrap();
+        other_ledger.add_next_block(&block_1).unwrap();
+        let block_2 = other_ledger.propose_next_block(&private_key, rng).unwrap();
+
+        // Deliver block 2 first, and ensure it is buffered as an orphan block.
+        let error = ledger.add_next_block(&block_2).unwrap_err();
+        assert!(error.downcast_ref::<BlockValidationError<CurrentNetwork>>().is_some());
+        assert_eq!(ledger.num_orphan_blocks(), 1);
+        assert_eq!(ledger.latest_height(), 0);
+
+        // Deliver block 1, and ensure both blocks are added.
+        ledger.add_next_block(&block_1).unwrap();
+        assert_eq!(ledger.num_orphan_blocks(), 0);
+        assert_eq!(ledger.latest_height(), 2);
+        assert_eq!(ledger.latest_hash(), block_2.hash());
+        assert_eq!(ledger.get_block(1).unwrap(), block_1);
+
+        // Ensure the buffer is capped, and disabled at zero.
+        other_ledger.add_next_block(&block_2).unwrap();
+        let block_3 = other_ledger.propose_next_block(&private_key, rng).unwrap();
+        other_ledger.add_next_block(&block_3).unwrap();
+        let block_4 = other_ledger.propose_next_block(&private_key, rng).unwrap();
+        other_ledger.add_next_block(&block_4).unwrap();
+        let block_5 = other_ledger.propose_next_block(&private_key, rng).unwrap();
+        assert!(ledger.add_next_block(&block_4).is_err());
+        assert!(ledger.add_next_block(&block_5).is_err());
+        assert_eq!(ledger.num_orphan_blocks(), 1);
+        ledger.set_max_orphan_blocks(0);
+        assert_eq!(ledger.num_orphan_blocks(), 0);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_contains_any() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample two genesis ledgers, with separate storage.
+        let ledger = test_helpers::sample_new_genesis_ledger(rng);
+        let mut other_ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Propose a clean block.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        other_ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = other_ledger.propose_next_block(&private_key, rng).unwrap();
+        let serial_numbers = next_block.serial_numbers().copied().collect::<Vec<_>>();
+        let commitments = next_block.commitments().copied().collect::<Vec<_>>();
+        let nonces = next_block.nonces().copied().collect::<Vec<_>>();
+
+        // Ensure the clean block contains no duplicates.
+        assert_eq!(ledger.contains_any(&serial_numbers, &commitments, &nonces).unwrap(), None);
+        assert_eq!(ledger.contains_any(&[], &[], &[]).unwrap(), None);
+
+        // Ensure each duplicate kind is detected, using the genesis block.
+        let genesis = ledger.get_block(0).unwrap();
+        let commitment = *genesis.commitments().next().unwrap();
+        let nonce = *genesis.nonces().next().unwrap();
+        assert_eq!(
+            ledger.contains_any(&serial_numbers, &[commitment], &nonces).unwrap(),
+            Some(DuplicateKind::Commitment(commitment))
+        );
+        assert_eq!(
+            ledger.contains_any(&serial_numbers, &commitments, &[nonce]).unwrap(),
+            Some(DuplicateKind::Nonce(nonce))
+        );
+
+        // Ensure a spent serial number is detected, once the block is added.
+        other_ledger.add_next_block(&next_block).unwrap();
+        let serial_number = serial_numbers[0];
+        assert_eq!(
+            other_ledger.contains_any(&serial_numbers, &[], &[]).unwrap(),
+            Some(DuplicateKind::SerialNumber(serial_number))
+        );
+        // Ensure the serial number is reported before the other duplicates.
+        assert_eq!(
+            other_ledger.contains_any(&[serial_number], &[commitment], &[nonce]).unwrap(),
+            Some(DuplicateKind::SerialNumber(serial_number))
+        );
+        // Ensure the clean block is still accepted by the ledger without it.
+        assert!(ledger.check_next_block(&next_block).is_ok());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_check_genesis_block() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Ensure the genesis block is accepted.
+        let genesis = test_helpers::sample_genesis_block_with_pk(rng, private_key);
+        CurrentLedger::check_genesis_block(&genesis).unwrap();
+
+        // Construct a genesis block with a transaction that spends a record.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        assert!(transaction.origins().next().is_some());
+        let transactions = Transactions::from(&[transaction]);
+        let header = Header::genesis(&transactions).unwrap();
+        let block = Block::new(&private_key, Default::default(), header, transactions, rng).unwrap();
+        assert!(block.is_genesis());
+
+        // Ensure the genesis block is rejected.
+        assert!(CurrentLedger::check_genesis_block(&block).is_err());
+        let address = Address::try_from(&private_key).unwrap();
+        assert!(CurrentLedger::new_with_genesis(&block, address, None).is_err());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_pause_resume() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Propose the next block.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+
+        // Ensure the block is not added while paused.
+        ledger.pause();
+        assert!(ledger.is_paused());
+        let error = ledger.add_next_block(&next_block).unwrap_err();
+        assert_eq!(error.downcast_ref::<LedgerPaused>(), Some(&LedgerPaused));
+        assert_eq!(ledger.latest_height(), 0);
+        // Ensure reads continue to work while paused.
+        assert_eq!(ledger.get_block(0).unwrap().hash(), ledger.latest_hash());
+        assert!(ledger.check_next_block(&next_block).is_ok());
+
+        // Ensure the block is added once resumed.
+        ledger.resume();
+        assert!(!ledger.is_paused());
+        ledger.add_next_block(&next_block).unwrap();
+        assert_eq!(ledger.latest_hash(), next_block.hash());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_latest_timestamp() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        assert_eq!(ledger.latest_timestamp(), ledger.latest_block().unwrap().header().timestamp());
+
+        // Add the next block.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Ensure the latest timestamp matches the latest block.
+        assert_eq!(ledger.latest_timestamp(), next_block.timestamp());
+        assert_eq!(ledger.latest_timestamp(), ledger.latest_block().unwrap().header().timestamp());
+
+        // Ensure the latest timestamp is restored when loading the ledger from storage.
+        let loaded = CurrentLedger::from(ledger.blocks.clone(), ledger.vm.program_store().clone()).unwrap();
+        assert_eq!(loaded.latest_timestamp(), next_block.timestamp());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_rollback_to_height() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        let genesis_hash = ledger.latest_hash();
+        let genesis_state_root = *ledger.latest_state_root();
+        let genesis_proof_target = ledger.cumulative_proof_target();
+
+        // Add a block with a deployment and an execution.
+        let deployment = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+        let execution = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(deployment.clone()).unwrap();
+        ledger.add_to_memory_pool(execution.clone()).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        assert_eq!(ledger.memory_pool_len(), 0);
+        // Add an empty block.
+        let empty_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&empty_block).unwrap();
+
+        // Ensure the ledger cannot roll back to the latest height or above.
+        assert!(ledger.rollback_to_height(2).is_err());
+
+        // Roll back both blocks, which is within the maximum reorg depth.
+        assert!(ledger.latest_height() <= CurrentNetwork::MAX_REORG_DEPTH);
+        ledger.rollback_to_height(0).unwrap();
+        assert_eq!(ledger.latest_height(), 0);
+        assert_eq!(ledger.latest_hash(), genesis_hash);
+        assert_eq!(*ledger.latest_state_root(), genesis_state_root);
+        assert_eq!(ledger.cumulative_proof_target(), genesis_proof_target);
+        assert!(!ledger.contains_block_hash(&next_block.hash()).unwrap());
+        assert!(!ledger.contains_block_hash(&empty_block.hash()).unwrap());
+        ledger.audit_block_tree().unwrap();
+        assert!(!ledger.contains_program_id(crate::ledger::vm::test_helpers::sample_program().id()).unwrap());
+
+        // Ensure the transactions of the displaced block are returned to the memory pool.
+        assert_eq!(ledger.memory_pool().keys().map(|id| *id).collect::<Vec<_>>(), vec![
+            deployment.id(),
+            execution.id()
+        ]);
+
+        // Ensure the transactions can be included in a new block.
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        assert!(ledger.contains_transaction_id(&deployment.id()).unwrap());
+        assert!(ledger.contains_transaction_id(&execution.id()).unwrap());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_rollback_to_height_with_finalize() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Deploy a program with a public mapping.
+        let program = test_helpers::deploy_public_mapping_program(&mut ledger, &private_key, "rollback_mapping", rng);
+        let mapping_name = Identifier::from_str("account").unwrap();
+
+        // Sample two recipients.
+        let first = Address::try_from(&PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        let second = Address::try_from(&PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        let get_amount = |ledger: &CurrentLedger, recipient: Address<CurrentNetwork>| {
+            let key = Plaintext::from_str(&recipient.to_string()).unwrap();
+            ledger.vm().program_store().get_value(program.id(), &mapping_name, &key).unwrap()
+        };
+
+        // Mint to the first recipient, and then to both recipients, in separate blocks.
+        for recipients in [vec![first], vec![first, second]] {
+            for recipient in recipients {
+                let transaction =
+                    test_helpers::sample_mint_public_transaction(&ledger, &private_key, &program, recipient, 3, rng);
+                ledger.add_to_memory_pool(transaction).unwrap();
+            }
+            let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+            ledger.add_next_block(&next_block).unwrap();
+        }
+        assert_eq!(ledger.latest_height(), 3);
+        assert_eq!(get_amount(&ledger, first), Some(Value::from_str("6u64").unwrap()));
+        assert_eq!(get_amount(&ledger, second), Some(Value::from_str("3u64").unwrap()));
+
+        // Roll back the latest block, and ensure the prior mapping values are restored.
+        ledger.rollback_to_height(2).unwrap();
+        assert_eq!(get_amount(&ledger, first), Some(Value::from_str("3u64").unwrap()));
+        assert_eq!(get_amount(&ledger, second), None);
+        ledger.verify_full().unwrap();
+
+        // Roll back to the deployment, and ensure the mapping is empty.
+        ledger.rollback_to_height(1).unwrap();
+        assert_eq!(get_amount(&ledger, first), None);
+        assert!(ledger.vm().program_store().program_key_values(program.id()).unwrap().is_empty());
+        ledger.verify_full().unwrap();
+
+        // Ensure the displaced executions can be finalized again in a new block.
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        assert_eq!(get_amount(&ledger, first), Some(Value::from_str("6u64").unwrap()));
+        assert_eq!(get_amount(&ledger, second), Some(Value::from_str("3u64").unwrap()));
+    }
+
+    #[test]
+    fn test_check_reorg_depth() {
+        let max_depth = CurrentNetwork::MAX_REORG_DEPTH;
+
+        // Ensure a rollback within the maximum reorg depth is accepted.
+        assert!(CurrentLedger::check_reorg_depth(1).is_ok());
+        assert!(CurrentLedger::check_reorg_depth(max_depth).is_ok());
+
+        // Ensure a rollback beyond the maximum reorg depth is rejected.
+        let error = CurrentLedger::check_reorg_depth(max_depth + 1).unwrap_err();
+        assert_eq!(error.downcast_ref::<ReorgTooDeep>(), Some(&ReorgTooDeep { depth: max_depth + 1, max_depth }));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_reset_to_genesis() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        let genesis_hash = ledger.latest_hash();
+
+        // Add a block with a deployment.
+        let deployment = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+        ledger.add_to_memory_pool(deployment.clone()).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        // Add an execution to the memory pool.
+        let execution = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(execution).unwrap();
+
+        // Ensure the ledger does not reset to a block that is not a genesis block, and is left unchanged.
+        assert!(ledger.reset_to_genesis(&next_block).is_err());
+        assert_eq!(ledger.latest_hash(), next_block.hash());
+        assert!(ledger.contains_block_hash(&genesis_hash).unwrap());
+        assert!(ledger.contains_transaction_id(&deployment.id()).unwrap());
+        assert_eq!(ledger.memory_pool_len(), 1);
+
+        // Sample a new genesis block.
+        let new_private_key = PrivateKey::new(rng).unwrap();
+        let new_address = Address::try_from(&new_private_key).unwrap();
+        let new_genesis =
+            CurrentLedger::generate_genesis(ledger.vm(), &new_private_key, new_address, 1_000_000, rng).unwrap();
+
+        // Ensure the ledger is restored, if the new genesis block fails to be added.
+        ledger.set_block_metadata(1, BlockMetadata::new(1_666_000_000, None)).unwrap();
+        let mut vetoed_ledger = ledger.clone();
+        vetoed_ledger.add_veto_hook(Arc::new(|_: &Block<CurrentNetwork>| bail!("Vetoed")));
+        assert!(vetoed_ledger.reset_to_genesis(&new_genesis).is_err());
+        assert_eq!(ledger.latest_hash(), next_block.hash());
+        assert_eq!(ledger.latest_block().unwrap(), next_block);
+        assert!(ledger.contains_block_hash(&genesis_hash).unwrap());
+        assert!(ledger.contains_transaction_id(&deployment.id()).unwrap());
+        assert!(ledger.contains_program_id(crate::ledger::vm::test_helpers::sample_program().id()).unwrap());
+        assert!(!ledger.contains_block_hash(&new_genesis.hash()).unwrap());
+        assert!(ledger.get_block_metadata(1).unwrap().is_some());
+        assert_eq!(ledger.memory_pool_len(), 1);
+
+        // Reset the ledger to the new genesis block.
+        ledger.reset_to_genesis(&new_genesis).unwrap();
+
+        // Ensure the ledger contains only the new genesis block.
+        assert_eq!(ledger.latest_height(), 0);
+        assert_eq!(ledger.latest_hash(), new_genesis.hash());
+        assert_eq!(ledger.latest_block().unwrap(), new_genesis);
+        assert!(!ledger.contains_block_hash(&genesis_hash).unwrap());
+        assert!(!ledger.contains_block_hash(&next_block.hash()).unwrap());
+        assert!(!ledger.contains_transaction_id(&deployment.id()).unwrap());
+        assert!(!ledger.contains_program_id(crate::ledger::vm::test_helpers::sample_program().id()).unwrap());
+        assert_eq!(ledger.memory_pool_len(), 0);
+        ledger.audit_block_tree().unwrap();
+
+        // Ensure the new genesis signer can add the next block.
+        let next_block = ledger.propose_next_block(&new_private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        assert_eq!(ledger.latest_height(), 1);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_from_out_of_order_storage() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add the next two blocks.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        for _ in 0..2 {
+            let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+            ledger.add_next_block(&next_block).unwrap();
+        }
+
+        // Store the blocks out of height order.
+        let blocks = BlockStore::<_, BlockMemory<_>>::open(None).unwrap();
+        for height in [2, 0, 1] {
+            blocks.insert(&ledger.get_block(height).unwrap()).unwrap();
+        }
+
+        // Ensure the loaded ledger has the same block tree.
+        let loaded = CurrentLedger::from(blocks, ProgramStore::<_, ProgramMemory<_>>::open(None).unwrap()).unwrap();
+        assert_eq!(loaded.latest_height(), 2);
+        assert_eq!(loaded.latest_hash(), ledger.latest_hash());
+        assert_eq!(loaded.latest_state_root(), ledger.latest_state_root());
+
+        // Ensure a ledger with a missing block cannot be loaded.
+        let blocks = BlockStore::<_, BlockMemory<_>>::open(None).unwrap();
+        for height in [2, 0] {
+            blocks.insert(&ledger.get_block(height).unwrap()).unwrap();
+        }
+        assert!(CurrentLedger::from(blocks, ProgramStore::<_, ProgramMemory<_>>::open(None).unwrap()).is_err());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_check_num_transitions() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add a transaction to the memory pool.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+
+        // Propose the next block.
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        let num_transitions = next_block.transitions().count();
+        assert!(num_transitions > 0);
+        assert!(num_transitions <= CurrentNetwork::MAX_TRANSITIONS_PER_BLOCK);
+
+        // Ensure a block within the limit is accepted.
+        assert!(CurrentLedger::check_num_transitions(next_block.transactions(), num_transitions).is_ok());
+        // Ensure a block exceeding the limit is rejected.
+        assert!(CurrentLedger::check_num_transitions(next_block.transactions(), num_transitions - 1).is_err());
+        assert!(CurrentLedger::check_num_transitions(next_block.transactions(), 0).is_err());
+
+        // Ensure the proposed block is accepted by the ledger.
+        ledger.add_next_block(&next_block).unwrap();
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_check_round_timestamp_skew() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Propose the next block.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        let mut other_ledger = ledger.clone();
+        other_ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = other_ledger.propose_next_block(&private_key, rng).unwrap();
+        assert!(ledger.check_next_block(&next_block).is_ok());
+
+        // Construct a block with a round number that is far ahead of its timestamp.
+        let metadata = Metadata::new(
+            CurrentNetwork::ID,
+            u64::MAX / 2,
+            next_block.height(),
+            next_block.header().coinbase_target(),
+            next_block.header().proof_target(),
+            next_block.timestamp(),
+        )
+        .unwrap();
+        let header =
+            Header::from(*ledger.latest_state_root(), next_block.transactions().to_root().unwrap(), metadata).unwrap();
+        let block =
+            Block::new(&private_key, ledger.latest_hash(), header, next_block.transactions().clone(), rng).unwrap();
+
+        // Ensure the inconsistent block is rejected.
+        assert!(ledger.check_next_block(&block).is_err());
+
+        // Ensure the skew is bounded by the tolerance.
+        let (round_time, tolerance) =
+            (CurrentNetwork::BLOCK_TIME_SECONDS, CurrentNetwork::MAX_ROUND_TIMESTAMP_SKEW_IN_SECS);
+        assert!(CurrentLedger::check_round_timestamp_skew(1, 100, 3, 100 + 2 * round_time).is_ok());
+        assert!(CurrentLedger::check_round_timestamp_skew(1, 100, 3, 100 + 2 * round_time - tolerance).is_ok());
+        assert!(CurrentLedger::check_round_timestamp_skew(1, 100, 3, 100 + 2 * round_time - tolerance - 1).is_err());
+        // Ensure a round that advances slower than the round time is accepted.
+        assert!(CurrentLedger::check_round_timestamp_skew(1, 100, 2, 100 + 100 * round_time).is_ok());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_verify_transaction() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis ledger.
+        let ledger = test_helpers::sample_genesis_ledger(rng);
+
+        // Ensure a valid transaction is verified.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        assert!(ledger.verify_transaction(&transaction).is_ok());
+
+        // Ensure a transaction with a tampered transaction ID is rejected.
+        let tampered = match transaction {
+            Transaction::Execute(_, execution, additional_fee) => {
+                let deployment = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+                Transaction::Execute(deployment.id(), execution, additional_fee)
+            }
+            _ => unreachable!("The sample execution transaction must be an execution"),
+        };
+        assert!(ledger.verify_transaction(&tampered).is_err());
+        // Ensure the tampered transaction is also rejected by the full check.
+        assert!(ledger.check_transaction(&tampered).is_err());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_audit_block_tree() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        assert!(ledger.audit_block_tree().is_ok());
+
+        // Add the next block, and ensure the audit passes.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        assert!(ledger.audit_block_tree().is_ok());
+
+        // Corrupt the block tree, by dropping the latest block hash.
+        ledger.block_tree = ledger.compute_block_tree(0).unwrap();
+        assert!(ledger.audit_block_tree().is_err());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_verify_full() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        assert!(ledger.verify_full().is_ok());
+
+        // Add the next block, and ensure the check passes.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        ledger.verify_full().unwrap();
+
+        // Load a ledger from a copy of the blocks, and ensure the check passes.
+        let storage = BlockMemory::<CurrentNetwork>::open(None).unwrap();
+        let blocks = BlockStore::from(storage.clone());
+        blocks.insert(&ledger.get_block(0).unwrap()).unwrap();
+        blocks.insert(&next_block).unwrap();
+        let tampered_ledger = CurrentLedger::from(blocks, ProgramStore::open(None).unwrap()).unwrap();
+        tampered_ledger.verify_full().unwrap();
+
+        // Tamper with the stored header of the next block.
+        let header = next_block.header();
+        let metadata = Metadata::new(
+            header.network(),
+            header.round(),
+            header.height(),
+            header.coinbase_target() - 1,
+            header.proof_target(),
+            header.timestamp(),
+        )
+        .unwrap();
+        let tampered = Header::from(*header.previous_state_root(), *header.transactions_root(), metadata).unwrap();
+        storage.header_map().insert(next_block.hash(), tampered).unwrap();
+
+        // Ensure the check fails on the tampered block, as its signature no longer matches its block hash.
+        let error = tampered_ledger.verify_full().unwrap_err().to_string();
+        assert!(error.contains("Block 1 is invalid"), "{error}");
+        assert!(error.contains("Invalid signature for block 1"), "{error}");
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_add_next_block_evicts_conflicts() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key and view key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        // Sample two genesis ledgers, with separate storage.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        let mut other_ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Retrieve an unspent record with a non-zero balance.
+        let (_, record) = test_helpers::sample_unspent_record(&ledger, &view_key);
+
+        // Create two different transactions that spend the same record.
+        let mut split = |amount: u64| {
+            Transaction::execute(
+                ledger.vm(),
+                &private_key,
+                &ProgramID::from_str("credits.aleo").unwrap(),
+                Identifier::from_str("split").unwrap(),
+                &[Value::Record(record.clone()), Value::from_str(&format!("{amount}u64")).unwrap()],
+                None,
+                rng,
+            )
+            .unwrap()
+        };
+        let local = split(1);
+        let received = split(2);
+        assert_ne!(local.id(), received.id());
+
+        // Add the local transaction to the memory pool.
+        ledger.add_to_memory_pool(local).unwrap();
+        assert_eq!(ledger.memory_pool_len(), 1);
+
+        // Receive a block containing the conflicting transaction, that was proposed by another ledger.
+        other_ledger.add_to_memory_pool(received.clone()).unwrap();
+        let next_block = other_ledger.propose_next_block(&private_key, rng).unwrap();
+        assert!(next_block.transactions().contains_key(&received.id()));
+        assert!(CurrentLedger::conflicts_with_block(&ledger.memory_pool().values().next().unwrap(), &next_block));
+
+        // Ensure the conflicting local transaction is evicted from the memory pool.
+        ledger.add_next_block(&next_block).unwrap();
+        assert_eq!(ledger.memory_pool_len(), 0);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_generate_genesis() {
+        let rng = &mut TestRng::default();
+
+        // Sample a private key and address.
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        let address = Address::try_from(&private_key).unwrap();
+
+        // Generate a genesis block.
+        let vm = crate::ledger::vm::test_helpers::sample_vm();
+        let genesis = CurrentLedger::generate_genesis(&vm, &private_key, address, 1_000_000, rng).unwrap();
+        assert!(genesis.is_genesis());
+
+        // Initialize a ledger from the genesis block.
+        let ledger = CurrentLedger::new_with_genesis(&genesis, address, None).unwrap();
+        assert_eq!(ledger.latest_height(), 0);
+        assert_eq!(ledger.latest_hash(), genesis.hash());
+        assert_eq!(ledger.block_signer(0).unwrap(), address);
+        // Ensure the genesis supply belongs to the private key.
+        assert!(ledger.find_records(&view_key, RecordsFilter::Unspent).unwrap().next().is_some());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_generate_genesis_with_recipient() {
+        let rng = &mut TestRng::default();
+
+        // Sample the private key of the genesis signer.
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        let address = Address::try_from(&private_key).unwrap();
+        // Sample the private key of the genesis recipient.
+        let recipient_private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let recipient_view_key = ViewKey::try_from(recipient_private_key).unwrap();
+        let recipient = Address::try_from(&recipient_private_key).unwrap();
+
+        // Ensure a genesis block with no supply is rejected.
+        let vm = crate::ledger::vm::test_helpers::sample_vm();
+        assert!(CurrentLedger::generate_genesis(&vm, &private_key, recipient, 0, rng).is_err());
+
+        // Generate a genesis block, with the supply minted to the recipient.
+        let supply = 5_000_000;
+        let genesis = CurrentLedger::generate_genesis(&vm, &private_key, recipient, supply, rng).unwrap();
+        let ledger = CurrentLedger::new_with_genesis(&genesis, address, None).unwrap();
+        assert_eq!(ledger.block_signer(0).unwrap(), address);
+
+        // Ensure the genesis supply belongs to the recipient.
+        let records = ledger.find_records(&recipient_view_key, RecordsFilter::Unspent).unwrap().collect::<Vec<_>>();
+        assert_eq!(records.len(), 1);
+        assert_eq!(**records[0].1.owner(), recipient);
+        assert_eq!(***records[0].1.gates(), supply);
+        // Ensure the genesis signer does not own a record.
+        assert!(ledger.find_records(&view_key, RecordsFilter::Unspent).unwrap().next().is_none());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_check_next_block_overflow() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Propose the next block.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        assert!(ledger.check_next_block(&next_block).is_ok());
+
+        // Ensure the block is rejected if the next block height overflows.
+        let current_height = ledger.current_height;
+        ledger.current_height = u32::MAX;
+        let error = ledger.check_next_block(&next_block).unwrap_err();
+        assert!(error.to_string().contains("overflows the latest block height"));
+        ledger.current_height = current_height;
+
+        // Ensure the block is rejected if the next round number overflows.
+        ledger.current_round = u64::MAX;
+        let error = ledger.check_next_block(&next_block).unwrap_err();
+        assert!(error.to_string().contains("overflows the latest round number"));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_next_round() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add the next block.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        assert!(ledger.latest_round() > 0);
+
+        // Ensure the proposal and the validation agree on the round, without timeouts.
+        let proposed_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        assert_eq!(proposed_block.round(), ledger.next_round(0).unwrap());
+        assert_eq!(ledger.next_round(0).unwrap(), ledger.latest_round() + 1);
+        ledger.check_next_block(&proposed_block).unwrap();
+
+        // Ensure a block with a round that includes timeouts is rejected, as blocks do not yet record timeouts.
+        let (header, transactions) = ledger.build_next_header().unwrap();
+        let metadata = Metadata::new(
+            header.network(),
+            ledger.next_round(2).unwrap(),
+            header.height(),
+            header.coinbase_target(),
+            header.proof_target(),
+            header.timestamp(),
+        )
+        .unwrap();
+        let header = Header::from(*header.previous_state_root(), *header.transactions_root(), metadata).unwrap();
+        let block = Block::new(&private_key, ledger.latest_hash(), header, transactions, rng).unwrap();
+        assert_eq!(block.round(), ledger.latest_round() + 3);
+        let error = ledger.check_next_block(&block).unwrap_err();
+        assert!(error.to_string().contains("incorrect round number"));
+
+        // Ensure the next round does not overflow.
+        ledger.current_round = u64::MAX - 1;
+        assert_eq!(ledger.next_round(0).unwrap(), u64::MAX);
+        assert!(ledger.next_round(1).is_err());
+        assert!(ledger.propose_next_block(&private_key, rng).is_ok());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_dust_threshold() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key, view key, and address.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        let address = Address::try_from(&view_key).unwrap();
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        assert_eq!(ledger.dust_threshold(), CurrentNetwork::DUST_THRESHOLD_IN_GATES);
+
+        // Deploy a program with a publicly-owned record.
+        let program = test_helpers::deploy_public_token_program(&mut ledger, &private_key, "public_dust", rng);
+
+        // Mint a token with a public balance of zero gates.
+        let transaction =
+            test_helpers::sample_public_mint_transaction(&ledger, &private_key, &program, address, 1, rng);
+
+        // Ensure the transaction is rejected when the dust threshold is set.
+        ledger.set_dust_threshold(1);
+        let error = ledger.add_to_memory_pool(transaction.clone()).unwrap_err();
+        assert!(error.to_string().contains("below the dust threshold"));
+        assert_eq!(ledger.memory_pool_len(), 0);
+
+        // Ensure the transaction is accepted when the dust threshold is disabled.
+        ledger.set_dust_threshold(0);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        assert_eq!(ledger.memory_pool_len(), 1);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_state_root_inclusion_proof() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Retrieve the genesis state root and block hash.
+        let genesis_state_root = *ledger.latest_state_root();
+        let genesis_hash = ledger.latest_hash();
+        // Ensure the genesis state root is proven in the genesis block tree.
+        let block_path = ledger.state_root_inclusion_proof(&genesis_state_root).unwrap();
+        assert!(CurrentNetwork::verify_merkle_path_bhp(&block_path, &genesis_state_root, &genesis_hash.to_bits_le()));
+
+        // Add the next blocks.
+        for transaction in [
+            crate::ledger::vm::test_helpers::sample_deployment_transaction(rng),
+            crate::ledger::vm::test_helpers::sample_execution_transaction(rng),
+        ] {
+            ledger.add_to_memory_pool(transaction).unwrap();
+            let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+            ledger.add_next_block(&next_block).unwrap();
+        }
+        assert_eq!(ledger.latest_height(), 2);
+
+        // Ensure the genesis state root is proven in the current block tree.
+        let state_root = *ledger.latest_state_root();
+        assert_ne!(state_root, genesis_state_root);
+        let block_path = ledger.state_root_inclusion_proof(&genesis_state_root).unwrap();
+        assert!(CurrentNetwork::verify_merkle_path_bhp(&block_path, &state_root, &genesis_hash.to_bits_le()));
+        assert!(!CurrentNetwork::verify_merkle_path_bhp(&block_path, &state_root, &ledger.latest_hash().to_bits_le()));
+
+        // Ensure an unknown state root is rejected.
+        assert!(ledger.state_root_inclusion_proof(&Field::from_u64(1234)).is_err());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_check_transaction_size() {
+        let rng = &mut TestRng::default();
+
+        // Sample a transaction.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        let num_bytes = transaction.to_bytes_le().unwrap().len();
+
+        // Ensure the transaction is accepted within the network maximum.
+        assert!(num_bytes <= CurrentNetwork::MAX_TRANSACTION_BYTES);
+        CurrentLedger::check_transaction_size(&transaction, CurrentNetwork::MAX_TRANSACTION_BYTES).unwrap();
+        CurrentLedger::check_transaction_size(&transaction, num_bytes).unwrap();
+
+        // Ensure the transaction is rejected, with its size, if it exceeds a smaller maximum.
+        let error = CurrentLedger::check_transaction_size(&transaction, num_bytes - 1).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains(&format!("is {num_bytes} bytes, which exceeds the maximum of {} bytes", num_bytes - 1)));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_block_metadata() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add the next block.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        assert_eq!(ledger.get_block_metadata(1).unwrap(), None);
+
+        // Retrieve the block hash and state root.
+        let block_hash = ledger.get_hash(1).unwrap();
+        let state_root = *ledger.latest_state_root();
+
+        // Set the metadata of the block.
+        let metadata = BlockMetadata::new(1_666_000_000, Some("127.0.0.1:4130".parse().unwrap()));
+        ledger.set_block_metadata(1, metadata.clone()).unwrap();
+        assert_eq!(ledger.get_block_metadata(1).unwrap(), Some(metadata));
+        assert_eq!(ledger.get_block_metadata(0).unwrap(), None);
+
+        // Ensure the metadata does not affect the block hash or the block tree.
+        assert_eq!(ledger.get_hash(1).unwrap(), block_hash);
+        assert_eq!(ledger.get_block(1).unwrap(), next_block);
+        assert_eq!(*ledger.latest_state_root(), state_root);
+        ledger.audit_block_tree().unwrap();
+
+        // Ensure metadata can not be set for a missing block.
+        assert!(ledger.set_block_metadata(2, BlockMetadata::new(1_666_000_000, None)).is_err());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_add_next_block_already_exists() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add the next block.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        let (state_root, cumulative_proof_target) = (*ledger.latest_state_root(), ledger.cumulative_proof_target());
+
+        // Ensure re-adding the same blocks succeeds, without changing the ledger state.
+        ledger.add_next_block(&next_block).unwrap();
+        ledger.add_next_block(&ledger.get_block(0).unwrap()).unwrap();
+        assert_eq!(ledger.latest_height(), 1);
+        assert_eq!(ledger.latest_hash(), next_block.hash());
+        assert_eq!(*ledger.latest_state_root(), state_root);
+        assert_eq!(ledger.cumulative_proof_target(), cumulative_proof_target);
+        assert_eq!(ledger.blocks.heights().count(), 2);
+        // Ensure a different block at an existing height is not fast-pathed.
+        let mut other_ledger = test_helpers::sample_new_genesis_ledger(rng);
+        let transaction = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+        other_ledger.add_to_memory_pool(transaction).unwrap();
+        let other_block = other_ledger.propose_next_block(&private_key, rng).unwrap();
+        assert_eq!(other_block.height(), next_block.height());
+        assert!(!ledger.contains_block(&other_block).unwrap());
+        assert!(ledger.add_next_block(&other_block).is_err());
+        assert_eq!(ledger.latest_hash(), next_block.hash());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_veto_hooks() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis private key.
+        let private_key = test_helpers::sample_genesis_private_key(rng);
+        // Sample the genesis ledger.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+
+        // Add a hook that rejects blocks deploying the sample program.
+        let program_id = *crate::ledger::vm::test_helpers::sample_program().id();
+        ledger.add_veto_hook(Arc::new(move |block: &Block<CurrentNetwork>| {
+            for transaction in block.transactions().values() {
+                if let Transaction::Deploy(_, deployment, _) = transaction {
+                    ensure!(*deployment.program_id() != program_id, "Program '{program_id}' is not allowed");
+                }
+            }
+            Ok(())
+        }));
+
+        // Ensure a block deploying the program is rejected.
+        let transaction = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+        ledger.add_to_memory_pool(transaction.clone()).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        let error = ledger.add_next_block(&next_block).unwrap_err();
+        assert!(error.to_string().contains("rejected by a veto hook"));
+        assert_eq!(ledger.latest_height(), 0);
+
+        // Ensure a block without the program is accepted.
+        ledger.clear_memory_pool().unwrap();
+        ledger.add_to_memory_pool(crate::ledger::vm::test_helpers::sample_execution_transaction(rng)).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+        assert_eq!(ledger.latest_height(), 1);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_memory_pool_root() {
+        let rng = &mut TestRng::default();
+
+        // Sample two genesis ledgers.
+        let mut ledger = test_helpers::sample_new_genesis_ledger(rng);
+        let mut other_ledger = test_helpers::sample_new_genesis_ledger(rng);
+        // Ensure empty memory pools produce identical roots.
+        assert_eq!(ledger.memory_pool_root().unwrap(), other_ledger.memory_pool_root().unwrap());
+
+        // Sample the transactions.
+        let deployment = crate::ledger::vm::test_helpers::sample_deployment_transaction(rng);
+        let execution = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+
+        // Ensure differing memory pools produce different roots.
+        ledger.add_to_memory_pool(deployment.clone()).unwrap();
+        assert_ne!(ledger.memory_pool_root().unwrap(), other_ledger.memory_pool_root().unwrap());
+
+        // Ensure identical memory pools produce identical roots, regardless of insertion order.
+        ledger.add_to_memory_pool(execution.clone()).unwrap();
+        other_ledger.add_to_memory_pool(execution).unwrap();
+        assert_ne!(ledger.memory_pool_root().unwrap(), other_ledger.memory_pool_root().unwrap());
+        other_ledger.add_to_memory_pool(deployment).unwrap();
+        assert_eq!(ledger.memory_pool_root().unwrap(), other_ledger.memory_pool_root().unwrap());
+    }
 }
@@ -0,0 +1,175 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use std::time::{Duration, Instant};
+
+/// The key by which memory pool submissions are rate limited.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RateLimitKey {
+    /// All submissions share a single bucket.
+    Global,
+    /// Submissions are limited per record origin, and submissions without record inputs share a single bucket.
+    Origin,
+}
+
+/// A token-bucket rate limit on memory pool submissions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    /// The maximum number of tokens in a bucket, i.e. the largest burst of submissions.
+    capacity: u32,
+    /// The interval at which one token is added to a bucket.
+    refill_interval: Duration,
+    /// The key by which submissions are limited.
+    key: RateLimitKey,
+}
+
+impl RateLimit {
+    /// Initializes a new rate limit, allowing bursts of `capacity` submissions per key,
+    /// and one further submission per key for each `refill_interval` that elapses.
+    pub fn new(capacity: u32, refill_interval: Duration, key: RateLimitKey) -> Result<Self> {
+        ensure!(capacity > 0, "The rate limit capacity must be nonzero");
+        ensure!(!refill_interval.is_zero(), "The rate limit refill interval must be nonzero");
+        Ok(Self { capacity, refill_interval, key })
+    }
+
+    /// Returns the maximum number of tokens in a bucket.
+    pub const fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Returns the interval at which one token is added to a bucket.
+    pub const fn refill_interval(&self) -> Duration {
+        self.refill_interval
+    }
+
+    /// Returns the key by which submissions are limited.
+    pub const fn key(&self) -> RateLimitKey {
+        self.key
+    }
+}
+
+/// The token bucket for a single key.
+#[derive(Copy, Clone, Debug)]
+struct Bucket {
+    /// The number of available tokens.
+    tokens: u32,
+    /// The time at which a token was last added, or the bucket was created.
+    last_refill: Instant,
+}
+
+/// The state of the memory pool rate limiter.
+#[derive(Clone, Debug)]
+pub(super) struct RateLimiter<N: Network> {
+    /// The rate limit.
+    limit: RateLimit,
+    /// The map of keys to buckets, where the `None` key is shared by submissions without a record origin.
+    /// Note: Full buckets are pruned, as they are equivalent to a new bucket.
+    buckets: IndexMap<Option<Origin<N>>, Bucket>,
+}
+
+impl<N: Network> RateLimiter<N> {
+    /// Initializes a new rate limiter with the given rate limit.
+    pub(super) fn new(limit: RateLimit) -> Self {
+        Self { limit, buckets: Default::default() }
+    }
+
+    /// Returns the rate limit.
+    pub(super) const fn limit(&self) -> RateLimit {
+        self.limit
+    }
+
+    /// Takes one token from each bucket keyed by the given transaction, at the given time.
+    /// If any of the buckets is empty, no tokens are taken, and `MemoryPoolRateLimited` is returned.
+    pub(super) fn acquire(&mut self, transaction: &Transaction<N>, now: Instant) -> Result<()> {
+        // Refill the buckets, and prune the full buckets.
+        let (capacity, interval) = (self.limit.capacity, self.limit.refill_interval);
+        self.buckets.retain(|_, bucket| {
+            let elapsed = now.saturating_duration_since(bucket.last_refill);
+            let refills = elapsed.as_nanos() / interval.as_nanos();
+            if refills > 0 {
+                bucket.tokens = capacity.min(bucket.tokens.saturating_add(refills.try_into().unwrap_or(u32::MAX)));
+                // Carry over the partial interval, so that the refill rate is exact.
+                bucket.last_refill += interval * (refills.min(u32::MAX as u128) as u32);
+            }
+            bucket.tokens < capacity
+        });
+
+        // Determine the keys of the transaction.
+        let keys = match self.limit.key {
+            RateLimitKey::Global => vec![None],
+            RateLimitKey::Origin => {
+                let origins = transaction.origins().copied().map(Some).collect::<IndexSet<_>>();
+                match origins.is_empty() {
+                    true => vec![None],
+                    false => origins.into_iter().collect(),
+                }
+            }
+        };
+
+        // Ensure each bucket has a token available.
+        let fresh = Bucket { tokens: capacity, last_refill: now };
+        if keys.iter().any(|key| self.buckets.get(key).unwrap_or(&fresh).tokens == 0) {
+            bail!(MemoryPoolRateLimited)
+        }
+        // Take one token from each bucket.
+        for key in keys {
+            self.buckets.entry(key).or_insert(fresh).tokens -= 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::network::Testnet3;
+    use snarkvm_utilities::TestRng;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_rate_limiter() {
+        let rng = &mut TestRng::default();
+
+        // Ensure an empty rate limit is rejected.
+        assert!(RateLimit::new(0, Duration::from_secs(1), RateLimitKey::Global).is_err());
+        assert!(RateLimit::new(1, Duration::ZERO, RateLimitKey::Global).is_err());
+
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        let limit = RateLimit::new(2, Duration::from_secs(10), RateLimitKey::Global).unwrap();
+        let mut limiter = RateLimiter::<CurrentNetwork>::new(limit);
+
+        // Ensure a burst of submissions is limited to the capacity.
+        let start = Instant::now();
+        assert!(limiter.acquire(&transaction, start).is_ok());
+        assert!(limiter.acquire(&transaction, start).is_ok());
+        let error = limiter.acquire(&transaction, start + Duration::from_secs(9)).unwrap_err();
+        assert_eq!(error.downcast_ref::<MemoryPoolRateLimited>(), Some(&MemoryPoolRateLimited));
+
+        // Ensure one token is added per refill interval.
+        assert!(limiter.acquire(&transaction, start + Duration::from_secs(10)).is_ok());
+        assert!(limiter.acquire(&transaction, start + Duration::from_secs(15)).is_err());
+        assert!(limiter.acquire(&transaction, start + Duration::from_secs(20)).is_ok());
+
+        // Ensure the bucket refills up to the capacity only.
+        let later = start + Duration::from_secs(1000);
+        assert!(limiter.acquire(&transaction, later).is_ok());
+        assert!(limiter.acquire(&transaction, later).is_ok());
+        assert!(limiter.acquire(&transaction, later).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+/// The outcome of simulating a transaction against the ledger.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimulationResult<N: Network> {
+    /// The `(commitment, record)` pairs, for all transition outputs that are records.
+    #[allow(clippy::type_complexity)]
+    output_records: Vec<(Field<N>, Record<N, Ciphertext<N>>)>,
+    /// The serial numbers, for all transition inputs that are records.
+    serial_numbers: Vec<Field<N>>,
+    /// The `(program ID, mapping name, key, value)` entries that are inserted or updated by `finalize`.
+    mapping_updates: Vec<MappingEntry<N>>,
+}
+
+impl<N: Network> SimulationResult<N> {
+    /// Returns the `(commitment, record)` pairs, for all transition outputs that are records.
+    #[allow(clippy::type_complexity)]
+    pub fn output_records(&self) -> &[(Field<N>, Record<N, Ciphertext<N>>)] {
+        &self.output_records
+    }
+
+    /// Returns the serial numbers, for all transition inputs that are records.
+    pub fn serial_numbers(&self) -> &[Field<N>] {
+        &self.serial_numbers
+    }
+
+    /// Returns the `(program ID, mapping name, key, value)` entries that are inserted or updated by `finalize`.
+    pub fn mapping_updates(&self) -> &[MappingEntry<N>] {
+        &self.mapping_updates
+    }
+}
+
+impl<N: Network, B: BlockStorage<N>, P: ProgramStorage<N>, M: for<'a> Map<'a, N::TransactionID, Transaction<N>>>
+    Ledger<N, B, P, M>
+{
+    /// Simulates the given transaction against a copy of the VM, and returns its outcome.
+    /// Note: The ledger is not modified.
+    pub fn simulate(&self, transaction: &Transaction<N>) -> Result<SimulationResult<N>> {
+        // Check that the transaction is well formed and unique.
+        self.check_transaction(transaction)?;
+
+        // Finalize the transaction in a copy of the VM.
+        let mut vm = self.vm.to_memory()?;
+        vm.finalize(transaction)?;
+
+        // Compute the mapping entries that are inserted or updated by the transaction.
+        // Note: A transaction may only update the mappings of the programs it deploys or executes.
+        let mut program_ids =
+            transaction.transitions().map(|transition| *transition.program_id()).collect::<IndexSet<_>>();
+        if let Transaction::Deploy(_, deployment, _) = transaction {
+            program_ids.insert(*deployment.program_id());
+        }
+        let mut mapping_updates = Vec::new();
+        for program_id in &program_ids {
+            for entry in vm.program_store().program_key_values(program_id)? {
+                // Retain the entry, if the key is new or its value has changed.
+                let (program_id, mapping_name, key, value) = &entry;
+                if self.vm.program_store().get_value(program_id, mapping_name, key)?.as_ref() != Some(value) {
+                    mapping_updates.push(entry);
+                }
+            }
+        }
+
+        Ok(SimulationResult {
+            output_records: transaction
+                .transitions()
+                .flat_map(Transition::output_records)
+                .map(|(commitment, record)| (*commitment, record.clone()))
+                .collect(),
+            serial_numbers: transaction.serial_numbers().copied().collect(),
+            mapping_updates,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_utilities::TestRng;
+
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_simulate() {
+        let rng = &mut TestRng::default();
+
+        // Sample the genesis ledger.
+        let ledger = crate::ledger::test_helpers::sample_genesis_ledger(rng);
+
+        // Simulate an execution.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        let result = ledger.simulate(&transaction).unwrap();
+
+        // Ensure the reported outputs match the transition outputs.
+        let expected = transaction
+            .transitions()
+            .flat_map(Transition::output_records)
+            .map(|(commitment, record)| (*commitment, record.clone()))
+            .collect::<Vec<_>>();
+        assert!(!expected.is_empty());
+        assert_eq!(result.output_records(), expected);
+        assert_eq!(result.serial_numbers(), transaction.serial_numbers().copied().collect::<Vec<_>>());
+        // Ensure the transfer has no 'finalize' side effects.
+        assert!(result.mapping_updates().is_empty());
+
+        // Ensure the ledger is not modified.
+        assert_eq!(ledger.latest_height(), 0);
+        assert!(!ledger.contains_transaction_id(&transaction.id()).unwrap());
+        assert!(ledger.check_transaction(&transaction).is_ok());
+
+        // Ensure an invalid transaction cannot be simulated.
+        let invalid = ledger.get_block(0).unwrap().transactions().values().next().unwrap().clone();
+        assert!(ledger.simulate(&invalid).is_err());
+
+        // Sample the genesis private key.
+        let private_key = crate::ledger::test_helpers::sample_genesis_private_key(rng);
+        // Sample a new genesis ledger, and deploy a program with a public mapping.
+        let mut ledger = crate::ledger::test_helpers::sample_new_genesis_ledger(rng);
+        let program = crate::ledger::test_helpers::deploy_public_mapping_program(
+            &mut ledger,
+            &private_key,
+            "simulate_mapping",
+            rng,
+        );
+        let recipient = Address::try_from(&private_key).unwrap();
+        let mapping_name = Identifier::from_str("account").unwrap();
+        let key = Plaintext::from_str(&recipient.to_string()).unwrap();
+
+        // Simulate a mint, and ensure the inserted mapping entry is reported.
+        let transaction = crate::ledger::test_helpers::sample_mint_public_transaction(
+            &ledger,
+            &private_key,
+            &program,
+            recipient,
+            3,
+            rng,
+        );
+        let result = ledger.simulate(&transaction).unwrap();
+        assert_eq!(result.mapping_updates(), vec![(
+            *program.id(),
+            mapping_name,
+            key.clone(),
+            Value::from_str("3u64").unwrap()
+        )]);
+        // Ensure the mapping is not modified.
+        assert_eq!(ledger.vm().program_store().get_value(program.id(), &mapping_name, &key).unwrap(), None);
+
+        // Add the mint to the ledger.
+        ledger.add_to_memory_pool(transaction).unwrap();
+        let next_block = ledger.propose_next_block(&private_key, rng).unwrap();
+        ledger.add_next_block(&next_block).unwrap();
+
+        // Simulate another mint, and ensure the updated mapping entry is reported.
+        let transaction = crate::ledger::test_helpers::sample_mint_public_transaction(
+            &ledger,
+            &private_key,
+            &program,
+            recipient,
+            4,
+            rng,
+        );
+        let result = ledger.simulate(&transaction).unwrap();
+        assert_eq!(result.mapping_updates(), vec![(
+            *program.id(),
+            mapping_name,
+            key.clone(),
+            Value::from_str("7u64").unwrap()
+        )]);
+        // Ensure the mapping is not modified.
+        assert_eq!(
+            ledger.vm().program_store().get_value(program.id(), &mapping_name, &key).unwrap(),
+            Some(Value::from_str("3u64").unwrap())
+        );
+    }
+}
@@ -25,14 +25,13 @@ pub use transition_leaf::*;
 
 mod verify;
 
+use crate::ledger::BLOCKS_DEPTH;
 use circuit::{
     collections::merkle_tree::MerklePath,
     network::Aleo,
     types::{environment::prelude::*, Boolean, Field},
 };
 
-/// The depth of the Merkle tree for the blocks.
-const BLOCKS_DEPTH: u8 = 32;
 /// The depth of the Merkle tree for the block header.
 const HEADER_DEPTH: u8 = 3;
 /// The depth of the Merkle tree for transactions in a block.
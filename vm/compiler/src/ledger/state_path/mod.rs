@@ -22,6 +22,7 @@ mod serialize;
 
 use crate::ledger::{
     BlockPath,
+    BlockTree,
     HeaderLeaf,
     HeaderPath,
     TransactionLeaf,
@@ -80,42 +81,86 @@ impl<N: Network> StatePath<N> {
         transition_path: TransitionPath<N>,
         transition_leaf: TransitionLeaf<N>,
     ) -> Result<Self> {
+        // Initialize the state path.
+        let state_path = Self {
+            state_root,
+            block_path,
+            block_hash,
+            previous_block_hash,
+            header_root,
+            header_path,
+            header_leaf,
+            transactions_path,
+            transaction_id,
+            transaction_path,
+            transaction_leaf,
+            transition_path,
+            transition_leaf,
+        };
+        // Ensure the Merkle paths are valid.
+        state_path.check_paths()?;
+        // Return the state path.
+        Ok(state_path)
+    }
+
+    /// Returns `true` if the state path is valid for the given state root.
+    /// Note: This does not require a ledger, as a verifier only needs to trust the given state root.
+    pub fn verify(&self, expected_state_root: &Field<N>) -> Result<bool> {
+        // Ensure the state root is the expected state root.
+        if *self.state_root != *expected_state_root {
+            return Ok(false);
+        }
+        // Ensure the Merkle paths, from the transition leaf to the state root, are valid.
+        Ok(self.check_paths().is_ok())
+    }
+
+    /// Returns `true` if the state path is valid, and its block hash is the leaf in the given block tree
+    /// at the block height of the state path.
+    /// Note: This allows a verifier to check the state path against a block tree from any point in time.
+    pub fn verify_against_tree(&self, tree: &BlockTree<N>) -> Result<bool> {
+        // Ensure the Merkle paths, from the transition leaf to the state root, are valid.
+        if self.check_paths().is_err() {
+            return Ok(false);
+        }
+        // Retrieve the block height from the block path.
+        let height = usize::try_from(*self.block_path.leaf_index())?;
+        // Ensure the block hash is the leaf in the block tree at the block height.
+        Ok(tree.prove(height, &self.block_hash.to_bits_le()).is_ok())
+    }
+
+    /// Checks the Merkle paths, from the transition leaf to the state root, are valid.
+    fn check_paths(&self) -> Result<()> {
+        let Self {
+            state_root,
+            block_path,
+            block_hash,
+            previous_block_hash,
+            header_root,
+            header_path,
+            header_leaf,
+            transactions_path,
+            transaction_id,
+            transaction_path,
+            transaction_leaf,
+            transition_path,
+            transition_leaf,
+        } = self;
+
         // Ensure the transition path is valid.
         ensure!(
-            N::verify_merkle_path_bhp(&transition_path, &transaction_leaf.id(), &transition_leaf.to_bits_le()),
+            N::verify_merkle_path_bhp(transition_path, &transaction_leaf.id(), &transition_leaf.to_bits_le()),
             "'{}' (an input or output ID) does not belong to '{}' (a function or transition)",
             transition_leaf.id(),
             transaction_leaf.id()
         );
         // Ensure the transaction path is valid.
         ensure!(
-            N::verify_merkle_path_bhp(&transaction_path, &transaction_id, &transaction_leaf.to_bits_le()),
+            N::verify_merkle_path_bhp(transaction_path, transaction_id, &transaction_leaf.to_bits_le()),
             "'{}' (a function or transition) does not belong to transaction '{transaction_id}'",
             transaction_leaf.id(),
         );
-        // Ensure the transactions path is valid.
-        ensure!(
-            N::verify_merkle_path_bhp(&transactions_path, &header_leaf.id(), &transaction_id.to_bits_le()),
-            "Transaction '{transaction_id}' does not belong to '{header_leaf}' (a header leaf)",
-        );
-        // Ensure the header path is valid.
-        ensure!(
-            N::verify_merkle_path_bhp(&header_path, &header_root, &header_leaf.to_bits_le()),
-            "'{header_leaf}' (a header leaf) does not belong to '{block_hash}' (a block header)",
-        );
-        // Ensure the block hash is correct.
-        let preimage = (*previous_block_hash).to_bits_le().into_iter().chain(header_root.to_bits_le().into_iter());
-        ensure!(
-            *block_hash == N::hash_bhp1024(&preimage.collect::<Vec<_>>())?,
-            "Block hash '{block_hash}' is incorrect. Double-check the previous block hash and block header root."
-        );
-        // Ensure the state root is correct.
-        ensure!(
-            N::verify_merkle_path_bhp(&block_path, &state_root, &block_hash.to_bits_le()),
-            "'{block_hash}' (a block hash) does not belong to '{state_root}' (a state root)",
-        );
-        // Return the state path.
-        Ok(Self {
+        // Ensure the Merkle paths, from the transaction ID to the state root, are valid.
+        check_transaction_paths(
             state_root,
             block_path,
             block_hash,
@@ -125,11 +170,7 @@ impl<N: Network> StatePath<N> {
             header_leaf,
             transactions_path,
             transaction_id,
-            transaction_path,
-            transaction_leaf,
-            transition_path,
-            transition_leaf,
-        })
+        )
     }
 
     /// Returns the state root.
@@ -197,3 +238,40 @@ impl<N: Network> StatePath<N> {
         &self.transition_leaf
     }
 }
+
+/// Checks the Merkle paths, from the transaction ID to the state root, are valid.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn check_transaction_paths<N: Network>(
+    state_root: &N::StateRoot,
+    block_path: &BlockPath<N>,
+    block_hash: &N::BlockHash,
+    previous_block_hash: &N::BlockHash,
+    header_root: &Field<N>,
+    header_path: &HeaderPath<N>,
+    header_leaf: &HeaderLeaf<N>,
+    transactions_path: &TransactionsPath<N>,
+    transaction_id: &N::TransactionID,
+) -> Result<()> {
+    // Ensure the transactions path is valid.
+    ensure!(
+        N::verify_merkle_path_bhp(transactions_path, &header_leaf.id(), &transaction_id.to_bits_le()),
+        "Transaction '{transaction_id}' does not belong to '{header_leaf}' (a header leaf)",
+    );
+    // Ensure the header path is valid.
+    ensure!(
+        N::verify_merkle_path_bhp(header_path, header_root, &header_leaf.to_bits_le()),
+        "'{header_leaf}' (a header leaf) does not belong to '{block_hash}' (a block header)",
+    );
+    // Ensure the block hash is correct.
+    let preimage = (**previous_block_hash).to_bits_le().into_iter().chain(header_root.to_bits_le());
+    ensure!(
+        **block_hash == N::hash_bhp1024(&preimage.collect::<Vec<_>>())?,
+        "Block hash '{block_hash}' is incorrect. Double-check the previous block hash and block header root."
+    );
+    // Ensure the state root is correct.
+    ensure!(
+        N::verify_merkle_path_bhp(block_path, state_root, &block_hash.to_bits_le()),
+        "'{block_hash}' (a block hash) does not belong to '{state_root}' (a state root)",
+    );
+    Ok(())
+}
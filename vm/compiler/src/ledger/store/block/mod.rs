@@ -31,13 +31,16 @@ use crate::{
         Block,
         Header,
         Signature,
+        Transaction,
         Transactions,
+        Transition,
     },
 };
-use console::network::prelude::*;
+use console::{account::Address, network::prelude::*, program::Owner};
 
 use anyhow::Result;
 use core::marker::PhantomData;
+use indexmap::IndexMap;
 use std::borrow::Cow;
 
 macro_rules! bail_with_block {
@@ -65,6 +68,8 @@ pub trait BlockStorage<N: Network>: Clone + Send + Sync {
     type TransitionStorage: TransitionStorage<N>;
     /// The mapping of `block hash` to `block signature`.
     type SignatureMap: for<'a> Map<'a, N::BlockHash, Signature<N>>;
+    /// The mapping of `address` to `[(block height, transaction ID)]`, for each publicly owned output record.
+    type AddressTransactionsMap: for<'a> Map<'a, Address<N>, Vec<(u32, N::TransactionID)>>;
 
     /// Initializes the block storage.
     fn open(dev: Option<u16>) -> Result<Self>;
@@ -83,6 +88,8 @@ pub trait BlockStorage<N: Network>: Clone + Send + Sync {
     fn transaction_store(&self) -> &TransactionStore<N, Self::TransactionStorage>;
     /// Returns the signature map.
     fn signature_map(&self) -> &Self::SignatureMap;
+    /// Returns the address transactions map.
+    fn address_transactions_map(&self) -> &Self::AddressTransactionsMap;
 
     /// Returns the transition store.
     fn transition_store(&self) -> &TransitionStore<N, Self::TransitionStorage> {
@@ -103,6 +110,7 @@ pub trait BlockStorage<N: Network>: Clone + Send + Sync {
         self.reverse_transactions_map().start_atomic();
         self.transaction_store().start_atomic();
         self.signature_map().start_atomic();
+        self.address_transactions_map().start_atomic();
     }
 
     /// Checks if an atomic batch is in progress.
@@ -114,6 +122,7 @@ pub trait BlockStorage<N: Network>: Clone + Send + Sync {
             || self.reverse_transactions_map().is_atomic_in_progress()
             || self.transaction_store().is_atomic_in_progress()
             || self.signature_map().is_atomic_in_progress()
+            || self.address_transactions_map().is_atomic_in_progress()
     }
 
     /// Aborts an atomic batch write operation.
@@ -125,6 +134,7 @@ pub trait BlockStorage<N: Network>: Clone + Send + Sync {
         self.reverse_transactions_map().abort_atomic();
         self.transaction_store().abort_atomic();
         self.signature_map().abort_atomic();
+        self.address_transactions_map().abort_atomic();
     }
 
     /// Finishes an atomic batch write operation.
@@ -135,7 +145,58 @@ pub trait BlockStorage<N: Network>: Clone + Send + Sync {
         self.transactions_map().finish_atomic()?;
         self.reverse_transactions_map().finish_atomic()?;
         self.transaction_store().finish_atomic()?;
-        self.signature_map().finish_atomic()
+        self.signature_map().finish_atomic()?;
+        self.address_transactions_map().finish_atomic()
+    }
+
+    /// Rebuilds the reverse block hash, reverse transaction, and address transaction maps,
+    /// and the reverse indices of the transactions, by scanning the blocks in height order.
+    fn rebuild_indices(&self) -> Result<()> {
+        // Retrieve the block hashes in ascending order of height.
+        let mut block_hashes = self.id_map().iter().map(|(height, hash)| (*height, *hash)).collect::<Vec<_>>();
+        block_hashes.sort_unstable_by_key(|(height, _)| *height);
+
+        // Collect the transaction IDs for each address, in height order.
+        let mut addresses = IndexMap::<Address<N>, Vec<(u32, N::TransactionID)>>::new();
+        for (height, block_hash) in &block_hashes {
+            let transactions = match self.get_block_transactions(block_hash)? {
+                Some(transactions) => transactions,
+                None => {
+                    bail!("Failed to rebuild the indices: missing transactions for block {height} ('{block_hash}')")
+                }
+            };
+            for (address, entries) in address_transactions(*height, transactions.values()) {
+                addresses.entry(address).or_default().extend(entries);
+            }
+        }
+
+        atomic_write_batch!(self, {
+            // Remove the reverse indices.
+            self.reverse_id_map().clear()?;
+            self.reverse_transactions_map().clear()?;
+            self.address_transactions_map().clear()?;
+
+            for (height, block_hash) in &block_hashes {
+                // Store the block height.
+                self.reverse_id_map().insert(*block_hash, *height)?;
+                // Store the reverse transaction IDs.
+                if let Some(transaction_ids) = self.transactions_map().get(block_hash)? {
+                    for transaction_id in transaction_ids.iter() {
+                        self.reverse_transactions_map().insert(*transaction_id, *block_hash)?;
+                    }
+                }
+            }
+            // Store the transaction IDs for each address.
+            for (address, address_transactions) in addresses {
+                self.address_transactions_map().insert(address, address_transactions)?;
+            }
+            // Rebuild the reverse indices of the transactions.
+            self.transaction_store().rebuild_indices()?;
+
+            Ok(())
+        });
+
+        Ok(())
     }
 
     /// Stores the given `block` into storage.
@@ -162,6 +223,16 @@ pub trait BlockStorage<N: Network>: Clone + Send + Sync {
             // Store the block signature.
             self.signature_map().insert(block.hash(), *block.signature())?;
 
+            // Store the transaction IDs for each address.
+            for (address, entries) in address_transactions(block.height(), block.transactions().values()) {
+                let mut address_transactions = match self.address_transactions_map().get(&address)? {
+                    Some(address_transactions) => cow_to_cloned!(address_transactions),
+                    None => Vec::new(),
+                };
+                address_transactions.extend(entries);
+                self.address_transactions_map().insert(address, address_transactions)?;
+            }
+
             Ok(())
         });
 
@@ -170,38 +241,77 @@ pub trait BlockStorage<N: Network>: Clone + Send + Sync {
 
     /// Removes the block for the given `block hash`.
     fn remove(&self, block_hash: &N::BlockHash) -> Result<()> {
-        // Retrieve the block height.
-        let height = match self.get_block_height(block_hash)? {
-            Some(height) => height,
-            None => bail!("Failed to remove block: missing block height for block hash '{block_hash}'"),
-        };
-        // Retrieve the transaction IDs.
-        let transaction_ids = match self.transactions_map().get(block_hash)? {
-            Some(transaction_ids) => transaction_ids,
-            None => bail!("Failed to remove block: missing transactions for block '{height}' ('{block_hash}')"),
-        };
+        self.remove_many(&[*block_hash])
+    }
+
+    /// Removes the blocks for the given `block hashes`, in one atomic batch.
+    fn remove_many(&self, block_hashes: &[N::BlockHash]) -> Result<()> {
+        // Retrieve the block height, transaction IDs, and transactions of each block.
+        let mut blocks = Vec::with_capacity(block_hashes.len());
+        for block_hash in block_hashes {
+            // Retrieve the block height.
+            let height = match self.get_block_height(block_hash)? {
+                Some(height) => height,
+                None => bail!("Failed to remove block: missing block height for block hash '{block_hash}'"),
+            };
+            // Retrieve the transaction IDs.
+            let transaction_ids = match self.transactions_map().get(block_hash)? {
+                Some(transaction_ids) => transaction_ids,
+                None => bail!("Failed to remove block: missing transactions for block '{height}' ('{block_hash}')"),
+            };
+            // Retrieve the transactions.
+            let transactions = match self.get_block_transactions(block_hash)? {
+                Some(transactions) => transactions,
+                None => bail!("Failed to remove block: missing transactions for block '{height}' ('{block_hash}')"),
+            };
+            blocks.push((height, block_hash, transaction_ids, transactions));
+        }
+
+        // Collect the transaction IDs to remove for each address, across the blocks.
+        // Note: This is done upfront, as the writes in an atomic batch are not visible to the reads in the batch.
+        let mut addresses = IndexMap::<Address<N>, Vec<(u32, N::TransactionID)>>::new();
+        for (height, _, _, transactions) in &blocks {
+            for (address, entries) in address_transactions(*height, transactions.values()) {
+                addresses.entry(address).or_default().extend(entries);
+            }
+        }
 
         atomic_write_batch!(self, {
-            // Remove the block hash.
-            self.id_map().remove(&height)?;
-            // Remove the block height.
-            self.reverse_id_map().remove(block_hash)?;
-            // Remove the block header.
-            self.header_map().remove(block_hash)?;
-
-            // Remove the transaction IDs.
-            self.transactions_map().remove(block_hash)?;
-
-            // Remove the block transactions.
-            for transaction_id in transaction_ids.iter() {
-                // Remove the reverse transaction ID.
-                self.reverse_transactions_map().remove(transaction_id)?;
-                // Remove the transaction.
-                self.transaction_store().remove(transaction_id)?;
+            for (height, block_hash, transaction_ids, _) in &blocks {
+                // Remove the block hash.
+                self.id_map().remove(height)?;
+                // Remove the block height.
+                self.reverse_id_map().remove(block_hash)?;
+                // Remove the block header.
+                self.header_map().remove(block_hash)?;
+
+                // Remove the transaction IDs.
+                self.transactions_map().remove(block_hash)?;
+
+                // Remove the block transactions.
+                for transaction_id in transaction_ids.iter() {
+                    // Remove the reverse transaction ID.
+                    self.reverse_transactions_map().remove(transaction_id)?;
+                    // Remove the transaction.
+                    self.transaction_store().remove(transaction_id)?;
+                }
+
+                // Remove the block signature.
+                self.signature_map().remove(block_hash)?;
             }
 
-            // Remove the block signature.
-            self.signature_map().remove(block_hash)?;
+            // Remove the transaction IDs for each address.
+            for (address, entries) in addresses {
+                let mut address_transactions = match self.address_transactions_map().get(&address)? {
+                    Some(address_transactions) => cow_to_cloned!(address_transactions),
+                    None => continue,
+                };
+                address_transactions.retain(|entry| !entries.contains(entry));
+                match address_transactions.is_empty() {
+                    true => self.address_transactions_map().remove(&address)?,
+                    false => self.address_transactions_map().insert(address, address_transactions)?,
+                }
+            }
 
             Ok(())
         });
@@ -217,6 +327,22 @@ pub trait BlockStorage<N: Network>: Clone + Send + Sync {
         }
     }
 
+    /// Returns up to `limit` of the `(block height, transaction ID)` pairs for the given `address`,
+    /// in order of block height, after skipping the first `offset` pairs.
+    /// Note: Only transactions that output a record publicly owned by the address are indexed,
+    /// as the owners of private records are not known to the ledger.
+    fn find_transaction_ids_for_public_owner(
+        &self,
+        address: &Address<N>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<(u32, N::TransactionID)>> {
+        match self.address_transactions_map().get(address)? {
+            Some(address_transactions) => Ok(address_transactions.iter().skip(offset).take(limit).copied().collect()),
+            None => Ok(Vec::new()),
+        }
+    }
+
     /// Returns the previous block hash of the given `block height`.
     fn get_previous_block_hash(&self, height: u32) -> Result<Option<N::BlockHash>> {
         match height.is_zero() {
@@ -315,8 +441,50 @@ pub trait BlockStorage<N: Network>: Clone + Send + Sync {
         };
 
         // Return the block.
-        Ok(Some(Block::from(previous_hash, header, transactions, signature)?))
+        Ok(Some(assemble_block(previous_hash, header, transactions, signature)?))
+    }
+}
+
+/// Assembles a block from the given previous block hash, block header, block transactions, and block signature,
+/// as they are stored in the block storage maps.
+pub fn assemble_block<N: Network>(
+    previous_hash: N::BlockHash,
+    header: Header<N>,
+    transactions: Transactions<N>,
+    signature: Signature<N>,
+) -> Result<Block<N>> {
+    // Ensure the genesis block does not have a previous block hash.
+    if header.height() == 0 && previous_hash != N::BlockHash::default() {
+        bail!("The genesis block cannot have a previous block hash ('{previous_hash}')")
+    }
+    // Construct the block.
+    Block::from(previous_hash, header, transactions, signature)
+}
+
+/// Returns the `(block height, transaction ID)` pairs for each address that publicly owns an output record
+/// in the given transactions.
+fn address_transactions<'a, N: Network>(
+    height: u32,
+    transactions: impl Iterator<Item = &'a Transaction<N>>,
+) -> IndexMap<Address<N>, Vec<(u32, N::TransactionID)>> {
+    let mut address_transactions = IndexMap::<_, Vec<_>>::new();
+    for transaction in transactions {
+        // Retrieve the public owners of the output records.
+        let owners = transaction.transitions().flat_map(Transition::output_records).filter_map(|(_, record)| {
+            match record.owner() {
+                Owner::Public(owner) => Some(*owner),
+                Owner::Private(..) => None,
+            }
+        });
+        for owner in owners {
+            let entries = address_transactions.entry(owner).or_default();
+            // Ensure the transaction is only added once for the address.
+            if !entries.contains(&(height, transaction.id())) {
+                entries.push((height, transaction.id()));
+            }
+        }
     }
+    address_transactions
 }
 
 /// An in-memory block storage.
@@ -336,6 +504,8 @@ pub struct BlockMemory<N: Network> {
     transaction_store: TransactionStore<N, TransactionMemory<N>>,
     /// The signature map.
     signature_map: MemoryMap<N::BlockHash, Signature<N>>,
+    /// The address transactions map.
+    address_transactions_map: MemoryMap<Address<N>, Vec<(u32, N::TransactionID)>>,
 }
 
 #[rustfmt::skip]
@@ -348,6 +518,7 @@ impl<N: Network> BlockStorage<N> for BlockMemory<N> {
     type TransactionStorage = TransactionMemory<N>;
     type TransitionStorage = TransitionMemory<N>;
     type SignatureMap = MemoryMap<N::BlockHash, Signature<N>>;
+    type AddressTransactionsMap = MemoryMap<Address<N>, Vec<(u32, N::TransactionID)>>;
 
     /// Initializes the block storage.
     fn open(dev: Option<u16>) -> Result<Self> {
@@ -364,6 +535,7 @@ impl<N: Network> BlockStorage<N> for BlockMemory<N> {
             reverse_transactions_map: MemoryMap::default(),
             transaction_store,
             signature_map: MemoryMap::default(),
+            address_transactions_map: MemoryMap::default(),
         })
     }
 
@@ -401,6 +573,11 @@ impl<N: Network> BlockStorage<N> for BlockMemory<N> {
     fn signature_map(&self) -> &Self::SignatureMap {
         &self.signature_map
     }
+
+    /// Returns the address transactions map.
+    fn address_transactions_map(&self) -> &Self::AddressTransactionsMap {
+        &self.address_transactions_map
+    }
 }
 
 /// The block store.
@@ -436,6 +613,16 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
         self.storage.remove(block_hash)
     }
 
+    /// Removes the blocks for the given `block hashes`, in one atomic batch.
+    pub fn remove_many(&self, block_hashes: &[N::BlockHash]) -> Result<()> {
+        self.storage.remove_many(block_hashes)
+    }
+
+    /// Rebuilds the reverse indices of the blocks and their transactions.
+    pub fn rebuild_indices(&self) -> Result<()> {
+        self.storage.rebuild_indices()
+    }
+
     /// Returns the transaction store.
     pub fn transaction_store(&self) -> &TransactionStore<N, B::TransactionStorage> {
         self.storage.transaction_store()
@@ -473,6 +660,11 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
         self.storage.get_block_hash(height)
     }
 
+    /// Returns the `(block height, block hash)` of each block in the range `start..end`, in ascending order of height.
+    pub fn get_block_hashes(&self, start: u32, end: u32) -> impl '_ + Iterator<Item = (u32, N::BlockHash)> {
+        self.storage.id_map().range(start, end).map(|(height, block_hash)| (*height, *block_hash))
+    }
+
     /// Returns the block height for the given `block hash`.
     pub fn get_block_height(&self, block_hash: &N::BlockHash) -> Result<Option<u32>> {
         self.storage.get_block_height(block_hash)
@@ -488,6 +680,14 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
         self.storage.get_block_transactions(block_hash)
     }
 
+    /// Returns the transaction IDs for the given `block hash`, without retrieving the transactions.
+    pub fn get_block_transaction_ids(&self, block_hash: &N::BlockHash) -> Result<Option<Vec<N::TransactionID>>> {
+        match self.storage.transactions_map().get(block_hash)? {
+            Some(transaction_ids) => Ok(Some(cow_to_cloned!(transaction_ids))),
+            None => Ok(None),
+        }
+    }
+
     /// Returns the block signature for the given `block hash`.
     pub fn get_block_signature(&self, block_hash: &N::BlockHash) -> Result<Option<Signature<N>>> {
         self.storage.get_block_signature(block_hash)
@@ -504,6 +704,71 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
     pub fn find_block_hash(&self, transaction_id: &N::TransactionID) -> Result<Option<N::BlockHash>> {
         self.storage.find_block_hash(transaction_id)
     }
+
+    /// Returns up to `limit` of the `(block height, transaction ID)` pairs for the given `address`,
+    /// in order of block height, after skipping the first `offset` pairs.
+    /// Note: Only transactions that output a record publicly owned by the address are indexed,
+    /// as the owners of private records are not known to the ledger.
+    pub fn find_transaction_ids_for_public_owner(
+        &self,
+        address: &Address<N>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<(u32, N::TransactionID)>> {
+        self.storage.find_transaction_ids_for_public_owner(address, offset, limit)
+    }
+}
+
+impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
+    /// Checks the integrity of the stored blocks.
+    pub fn verify_integrity(&self) -> Result<()> {
+        // Ensure the block heights are contiguous from the genesis block.
+        if let Some(height) = self.find_missing_height() {
+            bail!("Block {height} is missing, but a later block is stored")
+        }
+        // Ensure the block timestamps are monotonically increasing.
+        if let Some(height) = self.find_non_increasing_timestamp()? {
+            bail!("Block {height} has a timestamp that does not exceed the timestamp of block {}", height - 1)
+        }
+        Ok(())
+    }
+
+    /// Returns the lowest block height that is missing below the highest stored block height, if any.
+    pub fn find_missing_height(&self) -> Option<u32> {
+        // Retrieve the block heights in ascending order.
+        let mut heights = self.heights().map(|height| *height).collect::<Vec<_>>();
+        heights.sort_unstable();
+        // Find the first height that does not match its position.
+        heights.into_iter().zip(0u32..).find(|(height, expected)| height != expected).map(|(_, expected)| expected)
+    }
+
+    /// Returns the first block height whose timestamp does not exceed the timestamp of the previous block.
+    /// Note: The genesis block is skipped, as its timestamp is not set.
+    pub fn find_non_increasing_timestamp(&self) -> Result<Option<u32>> {
+        // Retrieve the block heights in ascending order.
+        let mut heights = self.heights().map(|height| *height).filter(|height| *height > 0).collect::<Vec<_>>();
+        heights.sort_unstable();
+
+        let mut previous: Option<(u32, i64)> = None;
+        for height in heights {
+            // Retrieve the block header.
+            let header = match self.get_block_hash(height)? {
+                Some(block_hash) => match self.get_block_header(&block_hash)? {
+                    Some(header) => header,
+                    None => bail!("Missing block header for block {height}"),
+                },
+                None => bail!("Missing block hash for block {height}"),
+            };
+            // Ensure the timestamp exceeds the timestamp of the previous block.
+            if let Some((previous_height, previous_timestamp)) = previous {
+                if previous_height + 1 == height && header.timestamp() <= previous_timestamp {
+                    return Ok(Some(height));
+                }
+            }
+            previous = Some((height, header.timestamp()));
+        }
+        Ok(None)
+    }
 }
 
 impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
@@ -533,6 +798,10 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ledger::Metadata;
+    use console::{network::Testnet3, types::Field};
+
+    type CurrentNetwork = Testnet3;
 
     #[test]
     fn test_insert_get_remove() {
@@ -564,6 +833,26 @@ mod tests {
         assert_eq!(None, candidate);
     }
 
+    #[test]
+    fn test_assemble_block() {
+        let mut rng = TestRng::default();
+
+        // Sample the block.
+        let block = crate::ledger::test_helpers::sample_genesis_block(&mut rng);
+
+        // Assemble the block from its components.
+        let candidate =
+            assemble_block(block.previous_hash(), *block.header(), block.transactions().clone(), *block.signature())
+                .unwrap();
+        assert_eq!(block, candidate);
+
+        // Ensure the block cannot be assembled with an incorrect previous block hash.
+        let previous_hash = Field::<CurrentNetwork>::from_u64(1).into();
+        assert!(
+            assemble_block(previous_hash, *block.header(), block.transactions().clone(), *block.signature()).is_err()
+        );
+    }
+
     #[test]
     fn test_find_block_hash() {
         let mut rng = TestRng::default();
@@ -604,4 +893,107 @@ mod tests {
             assert_eq!(None, candidate);
         }
     }
+
+    #[test]
+    fn test_rebuild_indices() {
+        let mut rng = TestRng::default();
+
+        // Sample the block.
+        let block = crate::ledger::test_helpers::sample_genesis_block(&mut rng);
+        let block_hash = block.hash();
+        let transaction_id = *block.transaction_ids().next().unwrap();
+
+        // Initialize a new block store, and insert the block.
+        let storage = BlockMemory::<CurrentNetwork>::open(None).unwrap();
+        let block_store = BlockStore::from(storage.clone());
+        block_store.insert(&block).unwrap();
+
+        // Corrupt the reverse indices.
+        let stale_hash = Field::<CurrentNetwork>::from_u64(1).into();
+        let stale_address = Address::new(Uniform::rand(&mut rng));
+        storage.reverse_id_map().remove(&block_hash).unwrap();
+        storage.reverse_id_map().insert(stale_hash, 1).unwrap();
+        storage.reverse_transactions_map().insert(transaction_id, stale_hash).unwrap();
+        storage.address_transactions_map().insert(stale_address, vec![(1, transaction_id)]).unwrap();
+        assert_eq!(block_store.get_block_height(&block_hash).unwrap(), None);
+        assert_eq!(block_store.find_block_hash(&transaction_id).unwrap(), Some(stale_hash));
+
+        // Rebuild the indices.
+        block_store.rebuild_indices().unwrap();
+
+        // Ensure the lookups are correct.
+        assert_eq!(block_store.get_block_height(&block_hash).unwrap(), Some(0));
+        assert!(!block_store.contains_block_hash(&stale_hash).unwrap());
+        for transaction_id in block.transaction_ids() {
+            assert_eq!(block_store.find_block_hash(transaction_id).unwrap(), Some(block_hash));
+        }
+        assert!(block_store.find_transaction_ids_for_public_owner(&stale_address, 0, 10).unwrap().is_empty());
+        assert_eq!(block_store.get_block(&block_hash).unwrap(), Some(block));
+    }
+
+    #[test]
+    fn test_verify_integrity_timestamps() {
+        let mut rng = TestRng::default();
+
+        // Sample the genesis block.
+        let block = crate::ledger::test_helpers::sample_genesis_block(&mut rng);
+
+        // Initialize a new block store, and insert the genesis block.
+        let block_store = BlockStore::<_, BlockMemory<_>>::open(None).unwrap();
+        block_store.insert(&block).unwrap();
+        block_store.verify_integrity().unwrap();
+
+        // Stores a header with the given height and timestamp.
+        let insert_header = |height: u32, timestamp: i64| {
+            let metadata = Metadata::new(CurrentNetwork::ID, height as u64, height, 0, 0, timestamp).unwrap();
+            let header = Header::from(*block.previous_state_root(), *block.transactions_root(), metadata).unwrap();
+            let block_hash = Field::<CurrentNetwork>::from_u32(height).into();
+            block_store.storage.id_map().insert(height, block_hash).unwrap();
+            block_store.storage.header_map().insert(block_hash, header).unwrap();
+        };
+
+        // Ensure increasing timestamps are accepted.
+        insert_header(1, 10);
+        insert_header(2, 20);
+        assert_eq!(block_store.find_non_increasing_timestamp().unwrap(), None);
+        block_store.verify_integrity().unwrap();
+
+        // Ensure a backwards timestamp is detected at the offending height.
+        insert_header(3, 15);
+        insert_header(4, 5);
+        assert_eq!(block_store.find_non_increasing_timestamp().unwrap(), Some(3));
+        assert!(block_store.verify_integrity().is_err());
+    }
+
+    #[test]
+    fn test_verify_integrity_heights() {
+        let mut rng = TestRng::default();
+
+        // Sample the genesis block.
+        let block = crate::ledger::test_helpers::sample_genesis_block(&mut rng);
+
+        // Initialize a new block store.
+        let block_store = BlockStore::<_, BlockMemory<_>>::open(None).unwrap();
+        assert_eq!(block_store.find_missing_height(), None);
+
+        // Stores a header with the given height.
+        let insert_header = |height: u32| {
+            let metadata = Metadata::new(CurrentNetwork::ID, height as u64, height, 0, 0, height as i64).unwrap();
+            let header = Header::from(*block.previous_state_root(), *block.transactions_root(), metadata).unwrap();
+            let block_hash = Field::<CurrentNetwork>::from_u32(height).into();
+            block_store.storage.id_map().insert(height, block_hash).unwrap();
+            block_store.storage.header_map().insert(block_hash, header).unwrap();
+        };
+
+        // Ensure a gap above the genesis block is detected, regardless of the insertion order.
+        block_store.insert(&block).unwrap();
+        insert_header(2);
+        assert_eq!(block_store.find_missing_height(), Some(1));
+        assert!(block_store.verify_integrity().is_err());
+
+        // Ensure contiguous heights are accepted.
+        insert_header(1);
+        assert_eq!(block_store.find_missing_height(), None);
+        block_store.verify_integrity().unwrap();
+    }
 }
@@ -31,6 +31,9 @@ use core::marker::PhantomData;
 use indexmap::{IndexMap, IndexSet};
 use std::collections::BTreeMap;
 
+/// A `(program ID, mapping name, key, value)` entry in a program mapping.
+pub type MappingEntry<N> = (ProgramID<N>, Identifier<N>, Plaintext<N>, Value<N>);
+
 /// A trait for program state storage. Note: For the program logic, see `DeploymentStorage`.
 ///
 /// We define the `mapping ID := Hash( program ID || mapping name )`,
@@ -698,6 +701,82 @@ impl<N: Network, P: ProgramStorage<N>> ProgramStore<N, P> {
     ) -> Result<Option<Value<N>>> {
         self.storage.get_value(program_id, mapping_name, key)
     }
+
+    /// Returns the `(program ID, mapping name, key, value)` entries, for all key-value pairs in storage.
+    pub fn key_values(&self) -> Result<Vec<MappingEntry<N>>> {
+        let mut key_values = Vec::new();
+        for (program_id_and_mapping_name, mapping_id) in self.storage.mapping_id_map().iter() {
+            let (program_id, mapping_name) = *program_id_and_mapping_name;
+            self.extend_key_values(&mut key_values, program_id, mapping_name, *mapping_id)?;
+        }
+        Ok(key_values)
+    }
+
+    /// Returns the `(program ID, mapping name, key, value)` entries, for all key-value pairs of the given `program ID`.
+    pub fn program_key_values(&self, program_id: &ProgramID<N>) -> Result<Vec<MappingEntry<N>>> {
+        let mut key_values = Vec::new();
+        for mapping_name in self.get_mapping_names(program_id)?.unwrap_or_default() {
+            let mapping_id = match self.storage.get_mapping_id(program_id, &mapping_name)? {
+                Some(mapping_id) => mapping_id,
+                None => {
+                    bail!("Illegal operation: mapping '{program_id}/{mapping_name}' is not initialized in storage.")
+                }
+            };
+            self.extend_key_values(&mut key_values, *program_id, mapping_name, mapping_id)?;
+        }
+        Ok(key_values)
+    }
+
+    /// Appends the `(program ID, mapping name, key, value)` entries of the given mapping to `key_values`.
+    fn extend_key_values(
+        &self,
+        key_values: &mut Vec<MappingEntry<N>>,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        mapping_id: Field<N>,
+    ) -> Result<()> {
+        // Retrieve the key IDs for the mapping.
+        let key_ids = match self.storage.key_value_id_map().get(&mapping_id)? {
+            Some(key_value_ids) => cow_to_cloned!(key_value_ids).into_keys(),
+            None => bail!("Illegal operation: mapping ID '{mapping_id}' is not initialized in storage."),
+        };
+        for key_id in key_ids {
+            // Retrieve the key and value.
+            let key = match self.storage.key_map().get(&key_id)? {
+                Some(key) => cow_to_cloned!(key),
+                None => bail!("Missing key for key ID '{key_id}' in mapping '{program_id}/{mapping_name}'"),
+            };
+            let value = match self.storage.get_value_from_key_id(&key_id)? {
+                Some(value) => value,
+                None => bail!("Missing value for key '{key}' in mapping '{program_id}/{mapping_name}'"),
+            };
+            key_values.push((program_id, mapping_name, key, value));
+        }
+        Ok(())
+    }
+}
+
+impl<N: Network, P: ProgramStorage<N>> ProgramStore<N, P> {
+    /// Returns a copy of the program store, backed by a new in-memory storage.
+    pub fn to_memory(&self) -> Result<ProgramStore<N, ProgramMemory<N>>> {
+        let storage = ProgramMemory::open(self.dev())?;
+        for (key, value) in self.storage.program_id_map().iter() {
+            storage.program_id_map().insert(*key, cow_to_cloned!(value))?;
+        }
+        for (key, value) in self.storage.mapping_id_map().iter() {
+            storage.mapping_id_map().insert(*key, *value)?;
+        }
+        for (key, value) in self.storage.key_value_id_map().iter() {
+            storage.key_value_id_map().insert(*key, cow_to_cloned!(value))?;
+        }
+        for (key, value) in self.storage.key_map().iter() {
+            storage.key_map().insert(*key, cow_to_cloned!(value))?;
+        }
+        for (key, value) in self.storage.value_map().iter() {
+            storage.value_map().insert(*key, cow_to_cloned!(value))?;
+        }
+        Ok(ProgramStore::from(storage))
+    }
 }
 
 #[cfg(test)]
@@ -129,6 +129,27 @@ pub trait DeploymentStorage<N: Network>: Clone + Send + Sync {
         self.transition_store().finish_atomic()
     }
 
+    /// Rebuilds the reverse program ID map, from the program IDs and editions of the deployments.
+    fn rebuild_indices(&self) -> Result<()> {
+        atomic_write_batch!(self, {
+            // Remove the reverse indices.
+            self.reverse_id_map().clear()?;
+
+            // Store the reverse program IDs.
+            for (transaction_id, program_id) in self.id_map().iter() {
+                let edition = match self.edition_map().get(&*program_id)? {
+                    Some(edition) => cow_to_copied!(edition),
+                    None => bail!("Failed to rebuild the indices: missing edition for program '{program_id}'"),
+                };
+                self.reverse_id_map().insert((*program_id, edition), *transaction_id)?;
+            }
+
+            Ok(())
+        });
+
+        Ok(())
+    }
+
     /// Stores the given `deployment transaction` pair into storage.
     fn insert(&self, transaction: &Transaction<N>) -> Result<()> {
         // Ensure the transaction is a deployment.
@@ -521,6 +542,11 @@ impl<N: Network, D: DeploymentStorage<N>> DeploymentStore<N, D> {
         self.storage.remove(transaction_id)
     }
 
+    /// Rebuilds the reverse program ID map.
+    pub fn rebuild_indices(&self) -> Result<()> {
+        self.storage.rebuild_indices()
+    }
+
     /// Starts an atomic batch write operation.
     pub fn start_atomic(&self) {
         self.storage.start_atomic();
@@ -591,6 +617,14 @@ impl<N: Network, D: DeploymentStorage<N>> DeploymentStore<N, D> {
         self.storage.get_certificate(program_id, function_name)
     }
 
+    /// Returns the transition IDs for the given `transaction ID`, i.e. the additional fee ID.
+    pub fn get_transition_ids(&self, transaction_id: &N::TransactionID) -> Result<Option<Vec<N::TransitionID>>> {
+        match self.storage.additional_fee_map().get(transaction_id)? {
+            Some(additional_fee_id) => Ok(Some(vec![cow_to_copied!(additional_fee_id)])),
+            None => Ok(None),
+        }
+    }
+
     /// Returns the additional fee for the given `transaction ID`.
     pub fn get_additional_fee(&self, transaction_id: &N::TransactionID) -> Result<Option<AdditionalFee<N>>> {
         self.storage.get_additional_fee(transaction_id)
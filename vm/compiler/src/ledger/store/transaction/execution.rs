@@ -93,6 +93,26 @@ pub trait ExecutionStorage<N: Network>: Clone + Send + Sync {
         self.transition_store().finish_atomic()
     }
 
+    /// Rebuilds the reverse transition ID map, from the transition IDs of the executions.
+    fn rebuild_indices(&self) -> Result<()> {
+        atomic_write_batch!(self, {
+            // Remove the reverse indices.
+            self.reverse_id_map().clear()?;
+
+            // Store the reverse transition IDs, including the additional fee IDs.
+            for (transaction_id, ids) in self.id_map().iter() {
+                let (transition_ids, optional_additional_fee_id) = &*ids;
+                for transition_id in transition_ids.iter().chain(optional_additional_fee_id) {
+                    self.reverse_id_map().insert(*transition_id, *transaction_id)?;
+                }
+            }
+
+            Ok(())
+        });
+
+        Ok(())
+    }
+
     /// Stores the given `execution transaction` pair into storage.
     fn insert(&self, transaction: &Transaction<N>) -> Result<()> {
         // Ensure the transaction is a execution.
@@ -350,6 +370,11 @@ impl<N: Network, E: ExecutionStorage<N>> ExecutionStore<N, E> {
         self.storage.remove(transaction_id)
     }
 
+    /// Rebuilds the reverse transition ID map.
+    pub fn rebuild_indices(&self) -> Result<()> {
+        self.storage.rebuild_indices()
+    }
+
     /// Returns the transition store.
     pub fn transition_store(&self) -> &TransitionStore<N, E::TransitionStorage> {
         self.storage.transition_store()
@@ -400,6 +425,17 @@ impl<N: Network, E: ExecutionStorage<N>> ExecutionStore<N, E> {
         }
     }
 
+    /// Returns the transition IDs for the given `transaction ID`, including the additional fee ID, if one exists.
+    pub fn get_transition_ids(&self, transaction_id: &N::TransactionID) -> Result<Option<Vec<N::TransitionID>>> {
+        match self.storage.id_map().get(transaction_id)? {
+            Some(ids) => {
+                let (transition_ids, optional_additional_fee_id) = cow_to_cloned!(ids);
+                Ok(Some(transition_ids.into_iter().chain(optional_additional_fee_id).collect()))
+            }
+            None => Ok(None),
+        }
+    }
+
     /// Returns the additional fee for the given `transaction ID`.
     pub fn get_additional_fee(&self, transaction_id: &N::TransactionID) -> Result<Option<AdditionalFee<N>>> {
         // Retrieve the optional additional fee ID.
@@ -109,6 +109,19 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
         self.execution_store().finish_atomic()
     }
 
+    /// Rebuilds the reverse indices of the deployments, executions, and transitions.
+    fn rebuild_indices(&self) -> Result<()> {
+        atomic_write_batch!(self, {
+            self.deployment_store().rebuild_indices()?;
+            self.execution_store().rebuild_indices()?;
+            self.transition_store().rebuild_indices()?;
+
+            Ok(())
+        });
+
+        Ok(())
+    }
+
     /// Stores the given `transaction` into storage.
     fn insert(&self, transaction: &Transaction<N>) -> Result<()> {
         atomic_write_batch!(self, {
@@ -262,6 +275,11 @@ impl<N: Network, T: TransactionStorage<N>> TransactionStore<N, T> {
         self.storage.remove(transaction_id)
     }
 
+    /// Rebuilds the reverse indices of the transactions.
+    pub fn rebuild_indices(&self) -> Result<()> {
+        self.storage.rebuild_indices()
+    }
+
     /// Returns the transition store.
     pub fn transition_store(&self) -> &TransitionStore<N, T::TransitionStorage> {
         self.storage.transition_store()
@@ -397,6 +415,20 @@ impl<N: Network, T: TransactionStorage<N>> TransactionStore<N, T> {
             TransactionType::Execute => self.storage.execution_store().get_additional_fee(transaction_id),
         }
     }
+
+    /// Returns the transition IDs for the given `transaction ID`, in the order of the transaction transitions.
+    pub fn get_transition_ids(&self, transaction_id: &N::TransactionID) -> Result<Option<Vec<N::TransitionID>>> {
+        // Retrieve the transaction type.
+        let transaction_type = match self.transaction_ids.get(transaction_id)? {
+            Some(transaction_type) => cow_to_copied!(transaction_type),
+            None => return Ok(None),
+        };
+        // Retrieve the transition IDs.
+        match transaction_type {
+            TransactionType::Deploy => self.storage.deployment_store().get_transition_ids(transaction_id),
+            TransactionType::Execute => self.storage.execution_store().get_transition_ids(transaction_id),
+        }
+    }
 }
 
 impl<N: Network, T: TransactionStorage<N>> TransactionStore<N, T> {
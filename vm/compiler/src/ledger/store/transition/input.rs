@@ -120,6 +120,30 @@ pub trait InputStorage<N: Network>: Clone + Send + Sync {
         self.external_record_map().finish_atomic()
     }
 
+    /// Rebuilds the reverse input ID map and the record tag map, from the input IDs and the records.
+    fn rebuild_indices(&self) -> Result<()> {
+        atomic_write_batch!(self, {
+            // Remove the reverse indices.
+            self.reverse_id_map().clear()?;
+            self.record_tag_map().clear()?;
+
+            // Store the reverse input IDs.
+            for (transition_id, input_ids) in self.id_map().iter() {
+                for input_id in input_ids.iter() {
+                    self.reverse_id_map().insert(*input_id, *transition_id)?;
+                }
+            }
+            // Store the record tags.
+            for (serial_number, record) in self.record_map().iter() {
+                self.record_tag_map().insert(record.0, *serial_number)?;
+            }
+
+            Ok(())
+        });
+
+        Ok(())
+    }
+
     /// Stores the given `(transition ID, input)` pair into storage.
     fn insert(&self, transition_id: N::TransitionID, inputs: &[Input<N>]) -> Result<()> {
         atomic_write_batch!(self, {
@@ -410,6 +434,11 @@ impl<N: Network, I: InputStorage<N>> InputStore<N, I> {
         self.storage.remove(transition_id)
     }
 
+    /// Rebuilds the reverse input ID map and the record tag map.
+    pub fn rebuild_indices(&self) -> Result<()> {
+        self.storage.rebuild_indices()
+    }
+
     /// Starts an atomic batch write operation.
     pub fn start_atomic(&self) {
         self.storage.start_atomic();
@@ -453,6 +482,15 @@ impl<N: Network, I: InputStorage<N>> InputStore<N, I> {
     pub fn find_transition_id(&self, input_id: &Field<N>) -> Result<Option<N::TransitionID>> {
         self.storage.find_transition_id(input_id)
     }
+
+    /// Returns the serial number for the given `tag`.
+    pub fn find_serial_number(&self, tag: &Field<N>) -> Result<Option<Field<N>>> {
+        match self.record_tag.get(tag)? {
+            Some(Cow::Borrowed(serial_number)) => Ok(Some(*serial_number)),
+            Some(Cow::Owned(serial_number)) => Ok(Some(serial_number)),
+            None => Ok(None),
+        }
+    }
 }
 
 impl<N: Network, I: InputStorage<N>> InputStore<N, I> {
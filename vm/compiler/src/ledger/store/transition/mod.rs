@@ -21,6 +21,7 @@ mod output;
 pub use output::*;
 
 use crate::{
+    atomic_write_batch,
     cow_to_cloned,
     cow_to_copied,
     ledger::{
@@ -150,6 +151,31 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
         self.fee_map().finish_atomic()
     }
 
+    /// Rebuilds the reverse `tpk` and `tcm` maps, and the reverse indices of the input and output stores.
+    fn rebuild_indices(&self) -> Result<()> {
+        atomic_write_batch!(self, {
+            // Remove the reverse indices.
+            self.reverse_tpk_map().clear()?;
+            self.reverse_tcm_map().clear()?;
+
+            // Store the reverse `tpk` entries.
+            for (transition_id, tpk) in self.tpk_map().iter() {
+                self.reverse_tpk_map().insert(*tpk, *transition_id)?;
+            }
+            // Store the reverse `tcm` entries.
+            for (transition_id, tcm) in self.tcm_map().iter() {
+                self.reverse_tcm_map().insert(*tcm, *transition_id)?;
+            }
+            // Rebuild the reverse indices of the inputs and outputs.
+            self.input_store().rebuild_indices()?;
+            self.output_store().rebuild_indices()?;
+
+            Ok(())
+        });
+
+        Ok(())
+    }
+
     /// Stores the given `transition` into storage.
     fn insert(&self, transition: Transition<N>) -> Result<()> {
         // Check if an atomic batch write is already in progress.
@@ -488,6 +514,11 @@ impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
         self.storage.remove(transition_id)
     }
 
+    /// Rebuilds the reverse indices of the transitions.
+    pub fn rebuild_indices(&self) -> Result<()> {
+        self.storage.rebuild_indices()
+    }
+
     /// Starts an atomic batch write operation.
     pub fn start_atomic(&self) {
         self.storage.start_atomic();
@@ -528,6 +559,11 @@ impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
         // Throw an error.
         bail!("Failed to find the transition ID for the given input or output ID '{id}'")
     }
+
+    /// Returns the serial number for the given `tag`.
+    pub fn find_serial_number(&self, tag: &Field<N>) -> Result<Option<Field<N>>> {
+        self.inputs.find_serial_number(tag)
+    }
 }
 
 impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
@@ -572,6 +608,14 @@ impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
         self.outputs.get_outputs(transition_id)
     }
 
+    /// Returns the transition public key for the given `transition ID`.
+    pub fn get_tpk(&self, transition_id: &N::TransitionID) -> Result<Option<Group<N>>> {
+        match self.tpk.get(transition_id)? {
+            Some(tpk) => Ok(Some(cow_to_copied!(tpk))),
+            None => Ok(None),
+        }
+    }
+
     /// Returns the finalize inputs for the given `transition ID`.
     pub fn get_finalize(&self, transition_id: &N::TransitionID) -> Result<Option<Vec<Value<N>>>> {
         match self.finalize.get(transition_id)? {
@@ -889,4 +933,38 @@ mod tests {
             assert_eq!(None, candidate);
         }
     }
+
+    #[test]
+    fn test_rebuild_indices() {
+        let rng = &mut TestRng::default();
+
+        // Sample a transition.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        let transition = transaction.transitions().next().unwrap().clone();
+        let transition_id = *transition.id();
+
+        // Initialize a new transition store, and insert the transition.
+        let storage = TransitionMemory::open(None).unwrap();
+        let transition_store = TransitionStore::from(storage.clone());
+        transition_store.insert(transition.clone()).unwrap();
+
+        // Corrupt the reverse indices.
+        let stale_tcm = Field::from_u64(1);
+        storage.reverse_tpk_map().remove(transition.tpk()).unwrap();
+        storage.reverse_tcm_map().insert(stale_tcm, transition_id).unwrap();
+        assert!(!transition_store.contains_tpk(transition.tpk()).unwrap());
+        assert!(transition_store.contains_tcm(&stale_tcm).unwrap());
+
+        // Rebuild the indices.
+        transition_store.rebuild_indices().unwrap();
+
+        // Ensure the lookups are correct.
+        assert!(transition_store.contains_tpk(transition.tpk()).unwrap());
+        assert!(transition_store.contains_tcm(transition.tcm()).unwrap());
+        assert!(!transition_store.contains_tcm(&stale_tcm).unwrap());
+        for id in transition.input_ids().chain(transition.output_ids()) {
+            assert_eq!(transition_store.find_transition_id(id).unwrap(), transition_id);
+        }
+        assert_eq!(transition_store.get_transition(&transition_id).unwrap(), Some(transition));
+    }
 }
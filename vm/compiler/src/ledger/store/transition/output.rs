@@ -14,9 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::ledger::{
-    map::{memory_map::MemoryMap, Map, MapRead},
-    transition::Output,
+use crate::{
+    atomic_write_batch,
+    ledger::{
+        map::{memory_map::MemoryMap, Map, MapRead},
+        transition::Output,
+    },
 };
 use console::{
     network::prelude::*,
@@ -117,6 +120,32 @@ pub trait OutputStorage<N: Network>: Clone + Send + Sync {
         self.external_record_map().finish_atomic()
     }
 
+    /// Rebuilds the reverse output ID map and the record nonce map, from the output IDs and the records.
+    fn rebuild_indices(&self) -> Result<()> {
+        atomic_write_batch!(self, {
+            // Remove the reverse indices.
+            self.reverse_id_map().clear()?;
+            self.record_nonce_map().clear()?;
+
+            // Store the reverse output IDs.
+            for (transition_id, output_ids) in self.id_map().iter() {
+                for output_id in output_ids.iter() {
+                    self.reverse_id_map().insert(*output_id, *transition_id)?;
+                }
+            }
+            // Store the record nonces.
+            for (commitment, record) in self.record_map().iter() {
+                if let (_, Some(record)) = &*record {
+                    self.record_nonce_map().insert(*record.nonce(), *commitment)?;
+                }
+            }
+
+            Ok(())
+        });
+
+        Ok(())
+    }
+
     /// Stores the given `(transition ID, output)` pair into storage.
     fn insert(&self, transition_id: N::TransitionID, outputs: &[Output<N>]) -> Result<()> {
         // Check if an atomic batch write is already in progress.
@@ -450,6 +479,11 @@ impl<N: Network, O: OutputStorage<N>> OutputStore<N, O> {
         self.storage.remove(transition_id)
     }
 
+    /// Rebuilds the reverse output ID map and the record nonce map.
+    pub fn rebuild_indices(&self) -> Result<()> {
+        self.storage.rebuild_indices()
+    }
+
     /// Starts an atomic batch write operation.
     pub fn start_atomic(&self) {
         self.storage.start_atomic();
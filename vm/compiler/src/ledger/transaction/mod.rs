@@ -150,6 +150,21 @@ impl<N: Network> Transaction<N> {
         }
     }
 
+    /// Returns `true` if the transaction is a coinbase transaction.
+    /// A coinbase transaction is an execution that mints credits, without consuming any records.
+    pub fn is_coinbase(&self) -> bool {
+        match self {
+            Self::Execute(_, execution, None) => {
+                // Ensure the execution does not consume any records.
+                self.serial_numbers().next().is_none()
+                    // Ensure every transition in the execution mints credits.
+                    && !execution.is_empty()
+                    && execution.iter().all(Transition::is_mint)
+            }
+            _ => false,
+        }
+    }
+
     /// Returns an iterator over all transitions.
     pub fn transitions(&self) -> impl '_ + Iterator<Item = &Transition<N>> {
         match self {
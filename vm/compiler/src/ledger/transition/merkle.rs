@@ -24,7 +24,55 @@ type TransitionTree<N> = BHPMerkleTree<N, TRANSITION_DEPTH>;
 /// The Merkle path for an input or output ID in the transition.
 pub type TransitionPath<N> = MerklePath<N, TRANSITION_DEPTH>;
 
+/// A proof that a record commitment is an output of a transition, which is verifiable against the transition ID.
+#[derive(Clone, PartialEq, Eq)]
+pub struct TransitionOutputProof<N: Network> {
+    /// The Merkle leaf for the record output.
+    leaf: TransitionLeaf<N>,
+    /// The Merkle path for the record output.
+    path: TransitionPath<N>,
+}
+
+impl<N: Network> TransitionOutputProof<N> {
+    /// Returns the Merkle leaf for the record output.
+    pub const fn leaf(&self) -> &TransitionLeaf<N> {
+        &self.leaf
+    }
+
+    /// Returns the Merkle path for the record output.
+    pub const fn path(&self) -> &TransitionPath<N> {
+        &self.path
+    }
+
+    /// Returns `true` if the proof shows the given commitment is a record output of the given transition.
+    pub fn verify(&self, transition_id: &N::TransitionID, commitment: &Field<N>) -> bool {
+        // Ensure the leaf is for the given commitment.
+        self.leaf.id() == *commitment
+            // Ensure the leaf is for a record output.
+            && self.leaf.variant() == RECORD_OUTPUT_VARIANT
+            // Ensure the Merkle path is valid for the transition ID.
+            && N::verify_merkle_path_bhp(&self.path, transition_id, &self.leaf.to_bits_le())
+    }
+}
+
+/// The variant of a record output, as in `Output::variant`.
+const RECORD_OUTPUT_VARIANT: u16 = 3;
+
 impl<N: Network> Transition<N> {
+    /// Returns a proof that the given commitment is a record output of this transition.
+    pub fn to_output_proof(&self, commitment: &Field<N>) -> Result<TransitionOutputProof<N>> {
+        // Ensure the commitment is a record output of this transition.
+        ensure!(
+            self.outputs.iter().any(|output| output.variant() == RECORD_OUTPUT_VARIANT && output.id() == commitment),
+            "Commitment '{commitment}' is not a record output of transition '{}'",
+            self.id
+        );
+        // Construct the Merkle leaf and path.
+        let leaf = self.to_leaf(commitment, false)?;
+        let path = self.to_path(&leaf)?;
+        Ok(TransitionOutputProof { leaf, path })
+    }
+
     /// Returns the transition root, by computing the root for a Merkle tree of the input and output IDs.
     pub fn to_root(&self) -> Result<Field<N>> {
         Ok(*self.to_tree()?.root())
@@ -142,9 +190,35 @@ impl<N: Network> Transition<N> {
 mod tests {
     use super::*;
     use console::network::Testnet3;
+    use snarkvm_utilities::TestRng;
 
     type CurrentNetwork = Testnet3;
 
+    #[test]
+    fn test_to_output_proof() {
+        let rng = &mut TestRng::default();
+
+        // Sample a transition with record outputs.
+        let transaction = crate::ledger::vm::test_helpers::sample_execution_transaction(rng);
+        let transition = transaction.transitions().find(|transition| transition.output_records().count() > 0).unwrap();
+
+        for (commitment, _) in transition.output_records() {
+            // Ensure the output proof verifies against the transition ID.
+            let proof = transition.to_output_proof(commitment).unwrap();
+            assert!(proof.verify(transition.id(), commitment));
+            // Ensure the output proof does not verify for another commitment or transition.
+            assert!(!proof.verify(transition.id(), &Field::rand(rng)));
+            let other_id = <CurrentNetwork as Network>::TransitionID::from(Field::rand(rng));
+            assert!(!proof.verify(&other_id, commitment));
+        }
+
+        // Ensure an output proof cannot be generated for a non-member commitment.
+        assert!(transition.to_output_proof(&Field::rand(rng)).is_err());
+        // Ensure an output proof cannot be generated for an input ID.
+        let input_id = transition.inputs().first().unwrap().id();
+        assert!(transition.to_output_proof(input_id).is_err());
+    }
+
     #[test]
     fn test_transition_depth() {
         // Ensure the log2 relationship between depth and the maximum number of transition inputs & outputs.
@@ -301,6 +301,19 @@ impl<N: Network> Transition<N> {
     pub const fn fee(&self) -> &i64 {
         &self.fee
     }
+
+    /// Returns `true` if the transition mints credits, via `credits.aleo/genesis` or `credits.aleo/mint`.
+    pub fn is_mint(&self) -> bool {
+        // Ensure the transition is for the credits program.
+        match ProgramID::<N>::from_str("credits.aleo") {
+            Ok(credits_program_id) if self.program_id == credits_program_id => (),
+            _ => return false,
+        }
+        // Ensure the function name is a minting function.
+        ["genesis", "mint"]
+            .iter()
+            .any(|name| matches!(Identifier::<N>::from_str(name), Ok(name) if self.function_name == name))
+    }
 }
 
 impl<N: Network> Transition<N> {
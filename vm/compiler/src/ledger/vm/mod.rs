@@ -25,7 +25,7 @@ mod verify;
 use crate::{
     cast_ref,
     ledger::{
-        store::{BlockStorage, BlockStore, ProgramStorage, ProgramStore},
+        store::{BlockStorage, BlockStore, ProgramMemory, ProgramStorage, ProgramStore},
         AdditionalFee,
         Transaction,
     },
@@ -93,6 +93,23 @@ impl<N: Network, P: ProgramStorage<N>> VM<N, P> {
         process!(self, logic)
     }
 
+    /// Returns the program store.
+    #[inline]
+    pub const fn program_store(&self) -> &ProgramStore<N, P> {
+        &self.store
+    }
+
+    /// Returns a copy of the VM, with a copy of the process and an in-memory copy of the program store.
+    /// Changes to the copy do not affect `self`.
+    #[inline]
+    pub fn to_memory(&self) -> Result<VM<N, ProgramMemory<N>>> {
+        Ok(VM {
+            process: Arc::new(RwLock::new(self.process.read().clone())),
+            store: self.store.to_memory()?,
+            _phantom: PhantomData,
+        })
+    }
+
     /// Deploys a program with the given program ID.
     #[inline]
     pub fn contains_program(&self, program_id: &ProgramID<N>) -> bool {
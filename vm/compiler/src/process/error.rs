@@ -0,0 +1,37 @@
+// Copyright (C) 2019-2022 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// The snarkVM library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkVM library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkVM library. If not, see <https://www.gnu.org/licenses/>.
+
+use console::network::prelude::*;
+
+/// A distinguished error that may occur when finalizing a transaction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FinalizeError {
+    /// The transaction evaluates more finalize commands than the step limit.
+    StepLimitExceeded { steps: usize, limit: usize },
+}
+
+impl Display for FinalizeError {
+    /// Prints the finalize error as a string.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::StepLimitExceeded { steps, limit } => {
+                write!(f, "The transaction evaluates {steps} finalize commands, exceeding the step limit of {limit}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FinalizeError {}
@@ -70,6 +70,9 @@ impl<N: Network> Process<N> {
             );
         }
 
+        // Ensure the execution does not exceed the finalize step limit.
+        Self::check_finalize_steps(self.count_finalize_steps(execution)?)?;
+
         // Replicate the execution stack for verification.
         let mut queue = execution.clone();
 
@@ -215,6 +218,10 @@ impl<N: Network> Process<N> {
             );
         }
 
+        // Ensure the execution does not exceed the finalize step limit.
+        // Note: This is checked before finalizing, so that the store is not updated by a partial execution.
+        Self::check_finalize_steps(self.count_finalize_steps(execution)?)?;
+
         // TODO (howardwu): This is a temporary approach. We should create a "CallStack" and recurse through the stack.
         //  Currently this loop assumes a linearly execution stack.
         // Finalize each transition, starting from the last one.
@@ -272,4 +279,26 @@ impl<N: Network> Process<N> {
 
         Ok(())
     }
+
+    /// Returns the number of finalize commands evaluated by the given execution.
+    fn count_finalize_steps(&self, execution: &Execution<N>) -> Result<usize> {
+        execution.iter().try_fold(0usize, |num_steps, transition| {
+            // Retrieve the stack.
+            let stack = self.get_stack(transition.program_id())?;
+            // Retrieve the number of commands in the finalize scope, if it exists.
+            let num_commands = match stack.get_function(transition.function_name())?.finalize() {
+                Some((_, finalize)) => finalize.commands().len(),
+                None => 0,
+            };
+            Ok(num_steps.saturating_add(num_commands))
+        })
+    }
+
+    /// Ensures the given number of finalize commands is within the step limit for a transaction.
+    pub(super) fn check_finalize_steps(num_steps: usize) -> Result<()> {
+        if num_steps > N::MAX_FINALIZE_STEPS {
+            bail!(FinalizeError::StepLimitExceeded { steps: num_steps, limit: N::MAX_FINALIZE_STEPS })
+        }
+        Ok(())
+    }
 }
@@ -17,6 +17,9 @@
 mod stack;
 pub use stack::*;
 
+mod error;
+pub use error::*;
+
 mod additional_fee;
 mod authorize;
 mod deploy;
@@ -1211,6 +1214,108 @@ finalize compute:
         assert_eq!(candidate, Value::from_str("0u64").unwrap());
     }
 
+    #[test]
+    fn test_check_finalize_steps() {
+        let limit = CurrentNetwork::MAX_FINALIZE_STEPS;
+
+        // Ensure a transaction within the step limit is accepted.
+        assert!(Process::<CurrentNetwork>::check_finalize_steps(0).is_ok());
+        assert!(Process::<CurrentNetwork>::check_finalize_steps(limit).is_ok());
+
+        // Ensure a transaction exceeding the step limit is rejected.
+        let error = Process::<CurrentNetwork>::check_finalize_steps(limit + 1).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<FinalizeError>(),
+            Some(&FinalizeError::StepLimitExceeded { steps: limit + 1, limit })
+        );
+    }
+
+    #[test]
+    fn test_process_execute_exceeds_finalize_steps() {
+        // Initialize a program with a finalize scope of 250 commands.
+        let program_string = format!(
+            r"
+program counter.aleo;
+
+mapping account:
+    key owner as address.public;
+    value amount as u64.public;
+
+function bump:
+    input r0 as address.public;
+    input r1 as u64.public;
+    add r1 r1 into r2;
+    output r2 as u64.public;
+    finalize r0 r1;
+
+finalize bump:
+    input r0 as address.public;
+    input r1 as u64.public;
+{}",
+            "    increment account[r0] by r1;\n    decrement account[r0] by r1;\n".repeat(125)
+        );
+        let (string, program0) = Program::<CurrentNetwork>::parse(&program_string).unwrap();
+        assert!(string.is_empty(), "Parser did not consume all of the string: '{string}'");
+
+        // Construct the process.
+        let mut process = super::test_helpers::sample_process(&program0);
+        // Initialize a program that calls the finalize scope five times.
+        let (string, program1) = Program::<CurrentNetwork>::parse(
+            r"
+import counter.aleo;
+
+program steps.aleo;
+
+function bump_many:
+    input r0 as address.public;
+    input r1 as u64.public;
+    call counter.aleo/bump r0 r1 into r2;
+    call counter.aleo/bump r0 r1 into r3;
+    call counter.aleo/bump r0 r1 into r4;
+    call counter.aleo/bump r0 r1 into r5;
+    call counter.aleo/bump r0 r1 into r6;
+    output r6 as u64.public;",
+        )
+        .unwrap();
+        assert!(string.is_empty(), "Parser did not consume all of the string: '{string}'");
+
+        // Add the program to the process.
+        process.add_program(&program1).unwrap();
+
+        // Initialize the RNG.
+        let rng = &mut TestRng::default();
+
+        // Initialize a new caller account.
+        let caller_private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let caller = Address::try_from(&caller_private_key).unwrap();
+
+        // Declare the input value.
+        let r0 = Value::<CurrentNetwork>::from_str(&caller.to_string()).unwrap();
+        let r1 = Value::<CurrentNetwork>::from_str("3u64").unwrap();
+
+        // Authorize the function call.
+        let function_name = Identifier::from_str("bump_many").unwrap();
+        let authorization = process
+            .authorize::<CurrentAleo, _>(&caller_private_key, program1.id(), function_name, &[r0, r1], rng)
+            .unwrap();
+        assert_eq!(authorization.len(), 6);
+
+        // Execute the request.
+        let (_, execution) = process.execute::<CurrentAleo, _>(authorization, rng).unwrap();
+        assert_eq!(execution.len(), 6);
+
+        // Ensure the execution is rejected, as it evaluates 5 * 250 finalize commands.
+        let limit = CurrentNetwork::MAX_FINALIZE_STEPS;
+        let expected = FinalizeError::StepLimitExceeded { steps: 1250, limit };
+        let error = process.verify_execution(&execution).unwrap_err();
+        assert_eq!(error.downcast_ref::<FinalizeError>(), Some(&expected));
+
+        // Ensure the execution is not finalized.
+        let store = ProgramStore::<_, ProgramMemory<_>>::open(None).unwrap();
+        let error = process.finalize_execution(&store, &execution).unwrap_err();
+        assert_eq!(error.downcast_ref::<FinalizeError>(), Some(&expected));
+    }
+
     #[test]
     fn test_process_execute_mint_public() {
         // Initialize a new program.